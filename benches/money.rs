@@ -4,9 +4,11 @@ use rust_decimal::prelude::FromPrimitive;
 
 
 
+#[allow(dead_code)]
 #[derive(Clone)]
 struct Currency(&'static str);
 
+#[allow(dead_code)]
 #[derive(Clone)]
 struct Monetary<T> {
     amount: T,
@@ -0,0 +1,695 @@
+// Monetary configuration
+// ======================
+//
+// `MonetaryConfig` gathers the settings every service used to wire by hand:
+// the default `MonetaryContext`, per-currency context overrides, custom
+// currencies and static exchange rates. It can be loaded from TOML or JSON
+// documents and overridden from `MONETARY_*` environment variables.
+//
+// ```toml
+// [context]
+// precision = 19
+// max_scale = 4
+// rounding_mode = "HALF_EVEN"
+//
+// [currency_contexts.JPY]
+// max_scale = 0
+//
+// [[currencies]]
+// code = "PTS"
+// numeric_code = 0
+// fraction_digits = 0
+// name = "Loyalty Points"
+// symbol = "pts"
+//
+// [[rates]]
+// base = "USD"
+// target = "EUR"
+// rate = "0.92"
+// ```
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use crate::constants::RoundingMode;
+use crate::core::{Monetizable, MonetaryContext, MonetaryContextBuilder};
+use crate::core::currency::Currency;
+use crate::core::currency_unit::CurrencyUnit;
+use crate::core::registry::CurrencyRegistry;
+use crate::errors::ConfigError;
+use crate::exchange::base_exchange::CurrencyConversion;
+use crate::exchange::static_exchange::StaticRateProvider;
+
+/// Prefix shared by every environment variable read by `MonetaryConfig`
+pub const ENV_PREFIX: &str = "MONETARY_";
+
+/// Partial context settings layered on top of the default context
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextOverride {
+    pub precision: Option<u32>,
+    pub max_scale: Option<i32>,
+    pub rounding_mode: Option<RoundingMode>,
+}
+
+impl ContextOverride {
+    /// Build a context from `base`, replacing the fields set on this override
+    pub fn apply(&self, base: &MonetaryContext) -> MonetaryContext {
+        MonetaryContextBuilder::new()
+            .with_precision(self.precision.unwrap_or(base.precision()))
+            .with_max_scale(self.max_scale.unwrap_or(base.max_scale()))
+            .with_rounding_mode(self.rounding_mode.unwrap_or(*base.rounding_mode()))
+            .build()
+    }
+
+    fn merge(&mut self, other: ContextOverride) {
+        if other.precision.is_some() {
+            self.precision = other.precision;
+        }
+        if other.max_scale.is_some() {
+            self.max_scale = other.max_scale;
+        }
+        if other.rounding_mode.is_some() {
+            self.rounding_mode = other.rounding_mode;
+        }
+    }
+}
+
+/// A static exchange rate from configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateConfig {
+    pub base: String,
+    pub target: String,
+    pub rate: Decimal,
+}
+
+/// Monetary settings loaded from a file or the environment
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MonetaryConfig {
+    default_context: MonetaryContext,
+    currency_contexts: HashMap<String, ContextOverride>,
+    currencies: Vec<Currency>,
+    rates: Vec<RateConfig>,
+}
+
+impl MonetaryConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a TOML document
+    pub fn from_toml_str(input: &str) -> Result<Self, ConfigError> {
+        let raw: RawConfig = toml::from_str(input)
+            .map_err(|e| ConfigError::parse("TOML", e.message()))?;
+        raw.into_config()
+    }
+
+    /// Parse a JSON document
+    pub fn from_json_str(input: &str) -> Result<Self, ConfigError> {
+        let raw: RawConfig = serde_json::from_str(input)
+            .map_err(|e| ConfigError::parse("JSON", e.to_string()))?;
+        raw.into_config()
+    }
+
+    /// Load a `.toml` or `.json` file, picking the format from the extension
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+
+        let parse: fn(&str) -> Result<Self, ConfigError> = match extension.as_deref() {
+            Some("toml") => Self::from_toml_str,
+            Some("json") => Self::from_json_str,
+            _ => return Err(ConfigError::unsupported_format(display)),
+        };
+
+        let contents = fs::read_to_string(path).map_err(|e| ConfigError::io(&display, e.to_string()))?;
+        parse(&contents)
+    }
+
+    /// Build a configuration from the process environment only
+    pub fn from_env() -> Result<Self, ConfigError> {
+        Self::default().with_env()
+    }
+
+    /// Apply `MONETARY_*` variables from the process environment on top of this
+    /// configuration. Variables whose name is not valid UTF-8 are ignored; a `MONETARY_*`
+    /// variable whose value is not is an error.
+    pub fn with_env(self) -> Result<Self, ConfigError> {
+        let mut vars = Vec::new();
+        for (key, value) in std::env::vars_os() {
+            let Some(key) = key.to_str().filter(|key| key.starts_with(ENV_PREFIX)) else {
+                continue;
+            };
+            let value = value
+                .into_string()
+                .map_err(|value| ConfigError::invalid_value(key, value.to_string_lossy(), "value is not valid UTF-8"))?;
+            vars.push((key.to_string(), value));
+        }
+        self.with_env_vars(vars)
+    }
+
+    /// Apply `MONETARY_*` variables on top of this configuration.
+    ///
+    /// Recognized keys:
+    /// - `MONETARY_PRECISION`, `MONETARY_MAX_SCALE`, `MONETARY_ROUNDING_MODE`
+    /// - `MONETARY_CONTEXT_<CODE>_PRECISION`, `..._MAX_SCALE`, `..._ROUNDING_MODE`
+    /// - `MONETARY_CURRENCY_<CODE>=<numeric>,<fraction digits>,<name>,<symbol>`
+    /// - `MONETARY_RATE_<BASE>_<TARGET>=<rate>`
+    ///
+    /// Variables without the prefix are ignored; unknown `MONETARY_*` keys are rejected
+    /// so that typos do not go unnoticed.
+    pub fn with_env_vars<I, K, V>(mut self, vars: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut default_override = ContextOverride::default();
+
+        for (key, value) in vars {
+            let (key, value) = (key.as_ref(), value.as_ref().trim());
+            let Some(setting) = key.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+
+            if let Some(field) = ContextField::from_name(setting) {
+                field.set(&mut default_override, key, value)?;
+            } else if let Some(rest) = setting.strip_prefix("CONTEXT_") {
+                let (code, field) = ContextField::split(rest)
+                    .ok_or_else(|| ConfigError::invalid_value(key, value, "unknown context setting"))?;
+                let mut entry = ContextOverride::default();
+                field.set(&mut entry, key, value)?;
+                self.currency_contexts
+                    .entry(code.to_uppercase())
+                    .or_default()
+                    .merge(entry);
+            } else if let Some(code) = setting.strip_prefix("CURRENCY_") {
+                let currency = parse_env_currency(key, code, value)?;
+                self.upsert_currency(currency);
+            } else if let Some(pair) = setting.strip_prefix("RATE_") {
+                let (base, target) = pair
+                    .split_once('_')
+                    .filter(|(base, target)| !base.is_empty() && !target.is_empty() && !target.contains('_'))
+                    .ok_or_else(|| ConfigError::invalid_value(key, value, "expected MONETARY_RATE_<BASE>_<TARGET>"))?;
+                let rate = parse_rate(key, value)?;
+                self.upsert_rate(RateConfig {
+                    base: base.to_uppercase(),
+                    target: target.to_uppercase(),
+                    rate,
+                });
+            } else {
+                return Err(ConfigError::invalid_value(key, value, "unknown setting"));
+            }
+        }
+
+        self.default_context = default_override.apply(&self.default_context);
+        self.validate()?;
+        Ok(self)
+    }
+
+    pub fn default_context(&self) -> &MonetaryContext {
+        &self.default_context
+    }
+
+    pub fn set_default_context(&mut self, context: MonetaryContext) {
+        self.default_context = context;
+    }
+
+    /// Per-currency overrides keyed by uppercase currency code
+    pub fn currency_contexts(&self) -> &HashMap<String, ContextOverride> {
+        &self.currency_contexts
+    }
+
+    /// Effective context for a currency: the default context with any override applied
+    pub fn context_for(&self, currency: &Currency) -> MonetaryContext {
+        self.context_for_code(currency.code())
+    }
+
+    pub fn context_for_code(&self, code: &str) -> MonetaryContext {
+        match self.currency_contexts.get(&code.to_uppercase()) {
+            Some(entry) => entry.apply(&self.default_context),
+            None => self.default_context.clone(),
+        }
+    }
+
+    /// Custom currencies declared in the configuration
+    pub fn currencies(&self) -> &[Currency] {
+        &self.currencies
+    }
+
+    pub fn rates(&self) -> &[RateConfig] {
+        &self.rates
+    }
+
+    /// Resolve a code against the custom currencies first, then the built-in registry
+    pub fn currency(&self, code: &str) -> Option<Currency> {
+        self.currencies
+            .iter()
+            .find(|c| c.code().eq_ignore_ascii_case(code))
+            .cloned()
+            .or_else(|| Currency::from_code(code))
+    }
+
+    /// Add the custom currencies to `registry`, replacing definitions with the same code
+    pub fn register_currencies(&self, registry: &CurrencyRegistry) -> Result<(), ConfigError> {
+        for currency in &self.currencies {
+            registry
+                .register_or_replace(currency.clone())
+                .map_err(|e| ConfigError::invalid_value("currencies.code", currency.code(), e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Scoped registry: the global currencies plus the custom ones from this configuration
+    pub fn registry(&self) -> Result<CurrencyRegistry, ConfigError> {
+        let registry = CurrencyRegistry::global().clone();
+        self.register_currencies(&registry)?;
+        Ok(registry)
+    }
+
+    /// Build a `StaticRateProvider` holding every configured rate
+    pub fn rate_provider(&self) -> Result<StaticRateProvider<Decimal>, ConfigError> {
+        let mut provider = StaticRateProvider::with_context(self.default_context.clone());
+        for rate in &self.rates {
+            let base = self.resolve(&rate.base, "rates.base")?;
+            let target = self.resolve(&rate.target, "rates.target")?;
+            provider.add_rate(&base, &target, rate.rate);
+        }
+        Ok(provider)
+    }
+
+    /// Build a conversion service backed by the configured static rates and currencies
+    pub fn conversion(&self) -> Result<CurrencyConversion<Decimal>, ConfigError> {
+        let mut conversion = CurrencyConversion::with_context(self.default_context.clone())
+            .with_registry(Arc::new(self.registry()?));
+        conversion.add_provider(Arc::new(self.rate_provider()?));
+        Ok(conversion)
+    }
+
+    fn resolve(&self, code: &str, key: &str) -> Result<Currency, ConfigError> {
+        self.currency(code)
+            .ok_or_else(|| ConfigError::invalid_value(key, code, "unknown currency"))
+    }
+
+    fn upsert_currency(&mut self, currency: Currency) {
+        match self.currencies.iter_mut().find(|c| c.code() == currency.code()) {
+            Some(existing) => *existing = currency,
+            None => self.currencies.push(currency),
+        }
+    }
+
+    fn upsert_rate(&mut self, rate: RateConfig) {
+        match self
+            .rates
+            .iter_mut()
+            .find(|r| r.base == rate.base && r.target == rate.target)
+        {
+            Some(existing) => *existing = rate,
+            None => self.rates.push(rate),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        validate_context("context", &self.default_context)?;
+        for code in self.currency_contexts.keys() {
+            let key = format!("currency_contexts.{}", code);
+            self.resolve(code, &key)?;
+            validate_context(&key, &self.context_for_code(code))?;
+        }
+        for rate in &self.rates {
+            self.resolve(&rate.base, "rates.base")?;
+            self.resolve(&rate.target, "rates.target")?;
+            if rate.rate <= Decimal::ZERO {
+                return Err(ConfigError::invalid_value("rates.rate", rate.rate.to_string(), "rate must be positive"));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_context(key: &str, context: &MonetaryContext) -> Result<(), ConfigError> {
+    if context.precision() == 0 {
+        return Err(ConfigError::invalid_value(
+            format!("{}.precision", key),
+            "0",
+            "precision must be at least 1",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Clone, Copy)]
+enum ContextField {
+    Precision,
+    MaxScale,
+    RoundingMode,
+}
+
+impl ContextField {
+    const SUFFIXES: [(&'static str, ContextField); 3] = [
+        ("PRECISION", ContextField::Precision),
+        ("MAX_SCALE", ContextField::MaxScale),
+        ("ROUNDING_MODE", ContextField::RoundingMode),
+    ];
+
+    fn from_name(setting: &str) -> Option<Self> {
+        Self::SUFFIXES
+            .iter()
+            .find(|(name, _)| setting == *name)
+            .map(|(_, field)| *field)
+    }
+
+    /// Split `<CODE>_<FIELD>` into the currency code and the field
+    fn split(setting: &str) -> Option<(&str, Self)> {
+        Self::SUFFIXES.iter().find_map(|(name, field)| {
+            setting
+                .strip_suffix(name)
+                .and_then(|rest| rest.strip_suffix('_'))
+                .filter(|code| !code.is_empty())
+                .map(|code| (code, *field))
+        })
+    }
+
+    fn set(self, target: &mut ContextOverride, key: &str, value: &str) -> Result<(), ConfigError> {
+        match self {
+            ContextField::Precision => {
+                target.precision = Some(parse_number(key, value)?);
+            }
+            ContextField::MaxScale => {
+                target.max_scale = Some(parse_number(key, value)?);
+            }
+            ContextField::RoundingMode => {
+                target.rounding_mode = Some(parse_rounding_mode(key, value)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_number<N: FromStr>(key: &str, value: &str) -> Result<N, ConfigError> {
+    value
+        .parse()
+        .map_err(|_| ConfigError::invalid_value(key, value, "expected an integer"))
+}
+
+fn parse_rounding_mode(key: &str, value: &str) -> Result<RoundingMode, ConfigError> {
+    RoundingMode::from_str(value)
+        .map_err(|_| ConfigError::invalid_value(key, value, "unknown rounding mode"))
+}
+
+fn parse_rate(key: &str, value: &str) -> Result<Decimal, ConfigError> {
+    Decimal::from_str(value)
+        .or_else(|_| Decimal::from_scientific(value))
+        .map_err(|_| ConfigError::invalid_value(key, value, "expected a decimal number"))
+}
+
+fn parse_env_currency(key: &str, code: &str, value: &str) -> Result<Currency, ConfigError> {
+    let fields: Vec<&str> = value.splitn(4, ',').map(str::trim).collect();
+    let [numeric_code, fraction_digits, name, symbol] = fields[..] else {
+        return Err(ConfigError::invalid_value(
+            key,
+            value,
+            "expected <numeric>,<fraction digits>,<name>,<symbol>",
+        ));
+    };
+
+    RawCurrency {
+        code: code.to_string(),
+        numeric_code: parse_number(key, numeric_code)?,
+        fraction_digits: parse_number(key, fraction_digits)?,
+        name: name.to_string(),
+        symbol: Some(symbol.to_string()),
+    }
+    .into_currency()
+}
+
+// Serialized representation
+// =========================
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawConfig {
+    context: RawContext,
+    currency_contexts: HashMap<String, RawContext>,
+    currencies: Vec<RawCurrency>,
+    rates: Vec<RawRate>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RawContext {
+    precision: Option<u32>,
+    max_scale: Option<i32>,
+    rounding_mode: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawCurrency {
+    code: String,
+    #[serde(default)]
+    numeric_code: i32,
+    fraction_digits: i32,
+    name: String,
+    symbol: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRate {
+    base: String,
+    target: String,
+    rate: RawRateValue,
+}
+
+// Rates may be written as strings to keep them exact, or as plain numbers
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawRateValue {
+    Text(String),
+    Integer(i64),
+    Float(f64),
+}
+
+impl RawConfig {
+    fn into_config(self) -> Result<MonetaryConfig, ConfigError> {
+        let mut config = MonetaryConfig {
+            default_context: self.context.into_override("context")?.apply(&MonetaryContext::default()),
+            ..MonetaryConfig::default()
+        };
+
+        for (code, context) in self.currency_contexts {
+            let key = format!("currency_contexts.{}", code);
+            config
+                .currency_contexts
+                .insert(code.to_uppercase(), context.into_override(&key)?);
+        }
+
+        for currency in self.currencies {
+            let currency = currency.into_currency()?;
+            if config.currencies.iter().any(|c| c.code() == currency.code()) {
+                return Err(ConfigError::invalid_value("currencies.code", currency.code(), "duplicate currency"));
+            }
+            config.currencies.push(currency);
+        }
+
+        for rate in self.rates {
+            let value = match rate.rate {
+                RawRateValue::Text(text) => parse_rate("rates.rate", &text)?,
+                RawRateValue::Integer(value) => Decimal::from(value),
+                RawRateValue::Float(value) => Decimal::try_from_f64(value)
+                    .map_err(|_| ConfigError::invalid_value("rates.rate", value.to_string(), "expected a finite number"))?,
+            };
+            config.upsert_rate(RateConfig {
+                base: rate.base.to_uppercase(),
+                target: rate.target.to_uppercase(),
+                rate: value,
+            });
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+impl RawContext {
+    fn into_override(self, key: &str) -> Result<ContextOverride, ConfigError> {
+        let rounding_mode = match self.rounding_mode {
+            Some(mode) => Some(parse_rounding_mode(&format!("{}.rounding_mode", key), &mode)?),
+            None => None,
+        };
+        Ok(ContextOverride {
+            precision: self.precision,
+            max_scale: self.max_scale,
+            rounding_mode,
+        })
+    }
+}
+
+impl RawCurrency {
+    fn into_currency(self) -> Result<Currency, ConfigError> {
+        let code = self.code.trim().to_uppercase();
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(ConfigError::invalid_value("currencies.code", self.code, "expected an alphanumeric code"));
+        }
+        if !(0..=28).contains(&self.fraction_digits) {
+            return Err(ConfigError::invalid_value(
+                "currencies.fraction_digits",
+                self.fraction_digits.to_string(),
+                "expected a value between 0 and 28",
+            ));
+        }
+
+        let symbol = self.symbol.unwrap_or_else(|| code.clone());
+        let unit = CurrencyUnit::new(&code, self.numeric_code, self.fraction_digits, &self.name);
+        Ok(Currency::new(unit, &symbol))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Monetary;
+
+    const TOML_CONFIG: &str = r#"
+        [context]
+        max_scale = 4
+        rounding_mode = "half_up"
+
+        [currency_contexts.jpy]
+        max_scale = 0
+
+        [[currencies]]
+        code = "PTS"
+        fraction_digits = 0
+        name = "Loyalty Points"
+        symbol = "pts"
+
+        [[rates]]
+        base = "USD"
+        target = "EUR"
+        rate = "0.92"
+
+        [[rates]]
+        base = "USD"
+        target = "PTS"
+        rate = 100
+    "#;
+
+    #[test]
+    fn test_load_toml() {
+        let config = MonetaryConfig::from_toml_str(TOML_CONFIG).unwrap();
+
+        assert_eq!(config.default_context(), &MonetaryContext::new(19, 4, RoundingMode::HalfUp));
+        assert_eq!(config.context_for(&Currency::jpy()), MonetaryContext::new(19, 0, RoundingMode::HalfUp));
+        assert_eq!(config.context_for(&Currency::usd()), *config.default_context());
+
+        let points = config.currency("pts").unwrap();
+        assert_eq!(points.display_name(), "Loyalty Points");
+        assert_eq!(points.symbol(), "pts");
+        assert_eq!(points.precision(), 0);
+        assert_eq!(config.rates().len(), 2);
+    }
+
+    #[test]
+    fn test_load_json_matches_toml() {
+        let json = r#"{
+            "context": { "max_scale": 4, "rounding_mode": "HALF_UP" },
+            "currency_contexts": { "JPY": { "max_scale": 0 } },
+            "currencies": [
+                { "code": "PTS", "fraction_digits": 0, "name": "Loyalty Points", "symbol": "pts" }
+            ],
+            "rates": [
+                { "base": "USD", "target": "EUR", "rate": "0.92" },
+                { "base": "USD", "target": "PTS", "rate": 100 }
+            ]
+        }"#;
+
+        assert_eq!(
+            MonetaryConfig::from_json_str(json).unwrap(),
+            MonetaryConfig::from_toml_str(TOML_CONFIG).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = [
+            ("PATH", "/usr/bin"),
+            ("MONETARY_MAX_SCALE", "2"),
+            ("MONETARY_CONTEXT_JPY_ROUNDING_MODE", "floor"),
+            ("MONETARY_CURRENCY_MILES", "0,0,Air Miles,mi"),
+            ("MONETARY_RATE_USD_EUR", "0.95"),
+        ];
+        let config = MonetaryConfig::from_toml_str(TOML_CONFIG)
+            .unwrap()
+            .with_env_vars(vars)
+            .unwrap();
+
+        assert_eq!(config.default_context(), &MonetaryContext::new(19, 2, RoundingMode::HalfUp));
+        // The file's max_scale override for JPY survives the env rounding override
+        assert_eq!(config.context_for_code("JPY"), MonetaryContext::new(19, 0, RoundingMode::Floor));
+        assert_eq!(config.currency("MILES").unwrap().symbol(), "mi");
+        assert_eq!(config.rates().iter().find(|r| r.target == "EUR").unwrap().rate, Decimal::new(95, 2));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_env_with_non_utf8_variables() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        // Names that are not UTF-8 and values of other variables are skipped
+        let not_utf8 = OsStr::from_bytes(b"caf\xe9");
+        std::env::set_var(OsStr::from_bytes(b"MONETARY_TEST_CAF\xc9"), "1");
+        std::env::set_var("OTHER_NOT_UTF8", not_utf8);
+        let ignored = MonetaryConfig::new().with_env();
+        std::env::remove_var(OsStr::from_bytes(b"MONETARY_TEST_CAF\xc9"));
+        std::env::remove_var("OTHER_NOT_UTF8");
+        assert!(ignored.is_ok());
+
+        std::env::set_var("MONETARY_TEST_NOT_UTF8", not_utf8);
+        let err = MonetaryConfig::new().with_env().unwrap_err();
+        std::env::remove_var("MONETARY_TEST_NOT_UTF8");
+        assert_eq!(err, ConfigError::invalid_value("MONETARY_TEST_NOT_UTF8", "caf\u{fffd}", "value is not valid UTF-8"));
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let err = MonetaryConfig::from_toml_str("[context]\nrounding_mode = \"sideways\"").unwrap_err();
+        assert_eq!(err.category(), "InvalidValue");
+
+        let err = MonetaryConfig::from_toml_str("[[rates]]\nbase = \"USD\"\ntarget = \"XYZ\"\nrate = 1").unwrap_err();
+        assert_eq!(err, ConfigError::invalid_value("rates.target", "XYZ", "unknown currency"));
+
+        let err = MonetaryConfig::from_json_str("{\"contxt\": {}}").unwrap_err();
+        assert_eq!(err.category(), "Parse");
+
+        let err = MonetaryConfig::new().with_env_vars([("MONETARY_PRECISON", "10")]).unwrap_err();
+        assert_eq!(err.category(), "InvalidValue");
+
+        let err = MonetaryConfig::from_file("monetary.yaml").unwrap_err();
+        assert_eq!(err, ConfigError::unsupported_format("monetary.yaml"));
+    }
+
+    #[test]
+    fn test_rate_provider_from_config() {
+        let config = MonetaryConfig::from_toml_str(TOML_CONFIG).unwrap();
+        let conversion = config.conversion().unwrap();
+
+        let amount = Monetary::new(Decimal::new(10, 0), Currency::usd());
+        let converted = conversion.convert(&amount, &Currency::eur()).unwrap();
+        assert_eq!(converted.amount, Decimal::new(92, 1));
+
+        let converted = conversion.convert_to_code(&amount, "PTS").unwrap();
+        assert_eq!(converted.currency, config.currency("PTS").unwrap());
+        assert_eq!(converted.amount, Decimal::new(1000, 0));
+        assert!(!Currency::is_supported("PTS"));
+    }
+}
@@ -1,3 +1,7 @@
+use std::fmt;
+use std::str::FromStr;
+use crate::errors::CurrencyError;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RoundingMode {
     Up,
@@ -10,5 +14,70 @@ pub enum RoundingMode {
     Unnecessary,
 }
 
+impl RoundingMode {
+    pub const ALL: [RoundingMode; 8] = [
+        RoundingMode::Up,
+        RoundingMode::Down,
+        RoundingMode::Ceiling,
+        RoundingMode::Floor,
+        RoundingMode::HalfUp,
+        RoundingMode::HalfDown,
+        RoundingMode::HalfEven,
+        RoundingMode::Unnecessary,
+    ];
+
+    /// Canonical upper snake case name, e.g. "HALF_EVEN"
+    pub fn name(&self) -> &'static str {
+        match self {
+            RoundingMode::Up => "UP",
+            RoundingMode::Down => "DOWN",
+            RoundingMode::Ceiling => "CEILING",
+            RoundingMode::Floor => "FLOOR",
+            RoundingMode::HalfUp => "HALF_UP",
+            RoundingMode::HalfDown => "HALF_DOWN",
+            RoundingMode::HalfEven => "HALF_EVEN",
+            RoundingMode::Unnecessary => "UNNECESSARY",
+        }
+    }
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// Accepts "HALF_EVEN", "half-even", "HalfEven" and similar spellings
+impl FromStr for RoundingMode {
+    type Err = CurrencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| *c != '_' && *c != '-' && !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+        RoundingMode::ALL
+            .iter()
+            .find(|mode| mode.name().replace('_', "") == normalized)
+            .copied()
+            .ok_or_else(|| CurrencyError::invalid_format_with_input("Unknown rounding mode", s))
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    #[test]
+    fn test_rounding_mode_from_str() {
+        assert_eq!("HALF_EVEN".parse::<RoundingMode>().unwrap(), RoundingMode::HalfEven);
+        assert_eq!("half-up".parse::<RoundingMode>().unwrap(), RoundingMode::HalfUp);
+        assert_eq!("Ceiling".parse::<RoundingMode>().unwrap(), RoundingMode::Ceiling);
+        assert!("sideways".parse::<RoundingMode>().is_err());
+
+        for mode in RoundingMode::ALL {
+            assert_eq!(mode.to_string().parse::<RoundingMode>().unwrap(), mode);
+        }
+    }
+}
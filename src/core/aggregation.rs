@@ -0,0 +1,399 @@
+// Aggregation
+// ===========
+//
+// Sums and order statistics over iterators of amounts. Every amount must be in the same
+// currency; a mismatch or an overflow stops the aggregation with an error. Inputs are
+// consumed as a stream (owned or borrowed), so only `median` buffers the amounts.
+// `MultiCurrencyTotal` keeps one running total per currency for mixed inputs.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::{btree_map, BTreeMap};
+use std::iter::Sum;
+use rust_decimal::Decimal;
+use crate::core::currency::Currency;
+use crate::core::currency_id::CurrencyId;
+use crate::core::{Monetary, Monetizable, MoneyError};
+use crate::errors::ExchangeError;
+use crate::exchange::base_exchange::CurrencyConversion;
+
+impl<T: Monetizable + 'static> Monetary<T> {
+    /// Total of `amounts`, in their common currency. Fails on an empty input, since
+    /// there is no currency to express zero in; use `checked_sum` for that.
+    pub fn sum<I, B>(amounts: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut amounts = amounts.into_iter();
+        let first = amounts.next().ok_or_else(|| MoneyError::EmptyAggregate("sum".to_string()))?;
+        amounts.try_fold(first.borrow().clone(), |mut total, amount| {
+            let amount = amount.borrow();
+            total.ensure_compatible(amount)?;
+            total.amount = total.amount.checked_add(amount.amount)?;
+            Ok(total)
+        })
+    }
+
+    /// Total of `amounts`, all of which must be in `currency`; zero for an empty input
+    pub fn checked_sum<I, B>(currency: &Currency, amounts: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut amounts = amounts.into_iter().peekable();
+        match amounts.peek() {
+            Some(first) => {
+                let first = first.borrow();
+                if !first.currency.same_currency(currency) {
+                    return Err(MoneyError::CurrencyMismatch(currency.code().to_string(), first.currency.code().to_string()));
+                }
+                Self::sum(amounts)
+            }
+            None => Ok(Self::zero(currency.clone())),
+        }
+    }
+
+    /// Arithmetic mean of `amounts`, unrounded
+    pub fn average<I, B>(amounts: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut count = 0u64;
+        let total = Self::sum(amounts.into_iter().inspect(|_| count += 1))
+            .map_err(|err| rename_empty(err, "average"))?;
+        total.checked_div(T::try_from_decimal(Decimal::from(count))?)
+    }
+
+    /// Smallest of `amounts`; the first one wins a tie
+    pub fn min<I, B>(amounts: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        Self::select(amounts, "min", Ordering::Less)
+    }
+
+    /// Largest of `amounts`; the first one wins a tie
+    pub fn max<I, B>(amounts: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        Self::select(amounts, "max", Ordering::Greater)
+    }
+
+    /// Middle value of `amounts`; the mean of the two middle values for an even count
+    pub fn median<I, B>(amounts: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut amounts = amounts.into_iter();
+        let first = amounts.next().ok_or_else(|| MoneyError::EmptyAggregate("median".to_string()))?;
+        let first = first.borrow();
+
+        let mut values = vec![first.amount];
+        for amount in amounts {
+            let amount = amount.borrow();
+            first.ensure_compatible(amount)?;
+            values.push(amount.amount);
+        }
+
+        let compare = |a: &T, b: &T| a.partial_cmp(b).unwrap_or(Ordering::Equal);
+        let (middle, odd) = (values.len() / 2, values.len() % 2 == 1);
+        let (lower, &mut upper, _) = values.select_nth_unstable_by(middle, compare);
+        let median = if odd {
+            upper
+        } else {
+            let below = *lower.iter().max_by(|a, b| compare(a, b)).expect("even count has a lower half");
+            below.checked_add(upper)?.checked_div(T::try_from_decimal(Decimal::TWO)?)?
+        };
+        Ok(first.with_same_currency(median))
+    }
+
+    fn select<I, B>(amounts: I, operation: &str, keep: Ordering) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Self>,
+    {
+        let mut amounts = amounts.into_iter();
+        let first = amounts.next().ok_or_else(|| MoneyError::EmptyAggregate(operation.to_string()))?;
+        amounts.try_fold(first.borrow().clone(), |selected, amount| {
+            let amount = amount.borrow();
+            selected.ensure_compatible(amount)?;
+            if amount.amount.partial_cmp(&selected.amount) == Some(keep) {
+                Ok(amount.clone())
+            } else {
+                Ok(selected)
+            }
+        })
+    }
+}
+
+fn rename_empty(err: MoneyError, operation: &str) -> MoneyError {
+    match err {
+        MoneyError::EmptyAggregate(_) => MoneyError::EmptyAggregate(operation.to_string()),
+        err => err,
+    }
+}
+
+/// `iter.sum::<Result<Monetary<T>, MoneyError>>()`, same as `Monetary::sum`
+impl<T: Monetizable + 'static> Sum<Monetary<T>> for Result<Monetary<T>, MoneyError> {
+    fn sum<I: Iterator<Item = Monetary<T>>>(iter: I) -> Self {
+        Monetary::sum(iter)
+    }
+}
+
+impl<'a, T: Monetizable + 'static> Sum<&'a Monetary<T>> for Result<Monetary<T>, MoneyError> {
+    fn sum<I: Iterator<Item = &'a Monetary<T>>>(iter: I) -> Self {
+        Monetary::sum(iter)
+    }
+}
+
+/// Running totals of amounts in several currencies, one per `CurrencyId`, iterated in
+/// id order (code order among codes of the same length)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiCurrencyTotal<T: Monetizable + 'static> {
+    totals: BTreeMap<CurrencyId, Monetary<T>>,
+}
+
+impl<T: Monetizable + 'static> MultiCurrencyTotal<T> {
+    pub fn new() -> Self {
+        Self { totals: BTreeMap::new() }
+    }
+
+    /// Totals of `amounts`, grouped by currency
+    pub fn from_amounts<I, B>(amounts: I) -> Result<Self, MoneyError>
+    where
+        I: IntoIterator<Item = B>,
+        B: Borrow<Monetary<T>>,
+    {
+        let mut totals = Self::new();
+        for amount in amounts {
+            totals.add(amount.borrow())?;
+        }
+        Ok(totals)
+    }
+
+    /// Add `amount` to the total of its currency
+    pub fn add(&mut self, amount: &Monetary<T>) -> Result<(), MoneyError> {
+        match self.totals.get_mut(&amount.currency.id()) {
+            Some(total) => total.amount = total.amount.checked_add(amount.amount)?,
+            None => {
+                self.totals.insert(amount.currency.id(), amount.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Take `amount` from the total of its currency; totals may go negative
+    pub fn subtract(&mut self, amount: &Monetary<T>) -> Result<(), MoneyError> {
+        match self.totals.get_mut(&amount.currency.id()) {
+            Some(total) => total.amount = total.amount.checked_sub(amount.amount)?,
+            None => {
+                let total = T::zero().checked_sub(amount.amount)?;
+                self.totals.insert(amount.currency.id(), amount.clone().with_amount(total));
+            }
+        }
+        Ok(())
+    }
+
+    /// Total in `currency`, if any amount in it was added
+    pub fn get(&self, currency: &Currency) -> Option<&Monetary<T>> {
+        self.totals.get(&currency.id())
+    }
+
+    pub fn currencies(&self) -> impl Iterator<Item = &Currency> {
+        self.totals.values().map(Monetary::currency)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Monetary<T>> {
+        self.totals.values()
+    }
+
+    /// Number of distinct currencies
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+}
+
+impl<T: Monetizable + Send + Sync + 'static> MultiCurrencyTotal<T> {
+    /// Convert every total into `target` and add them up; zero when nothing was added.
+    /// Fails with the first total that could not be converted or added.
+    pub fn collapse(&self, target: &Currency, conversion: &CurrencyConversion<T>) -> Result<Monetary<T>, ExchangeError> {
+        self.collapse_all(target, conversion).map_err(|mut failures| failures.swap_remove(0))
+    }
+
+    /// Like `collapse`, but fails with one error per total that could not be converted
+    /// or added, each naming the pair it was converting
+    pub fn collapse_all(
+        &self,
+        target: &Currency,
+        conversion: &CurrencyConversion<T>
+    ) -> Result<Monetary<T>, Vec<ExchangeError>> {
+        let mut total = Monetary::<T>::zero(target.clone());
+        let mut failures = Vec::new();
+        for amount in self.totals.values() {
+            let added = conversion.convert(amount, target).and_then(|converted| {
+                total.amount.checked_add(converted.amount)
+                    .map_err(|err| ExchangeError::conversion_error(amount.currency.code(), target.code(), err))
+            });
+            match added {
+                Ok(sum) => total.amount = sum,
+                Err(err) => failures.push(err),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(total)
+        } else {
+            Err(failures)
+        }
+    }
+}
+
+impl<T: Monetizable + 'static> Default for MultiCurrencyTotal<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Monetizable + 'static> IntoIterator for &'a MultiCurrencyTotal<T> {
+    type Item = &'a Monetary<T>;
+    type IntoIter = btree_map::Values<'a, CurrencyId, Monetary<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.totals.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use crate::core::currency_unit::CurrencyUnit;
+    use crate::exchange::static_exchange::StaticRateProvider;
+
+    fn money(value: &str, currency: Currency) -> Monetary<Decimal> {
+        Monetary::new(Decimal::from_str(value).unwrap(), currency)
+    }
+
+    fn usd(values: &[&str]) -> Vec<Monetary<Decimal>> {
+        values.iter().map(|value| money(value, Currency::usd())).collect()
+    }
+
+    #[test]
+    fn test_sum() {
+        let items = usd(&["0.10", "0.20", "19.99", "-5.00"]);
+        assert_eq!(Monetary::sum(&items), Ok(money("15.29", Currency::usd())));
+        assert_eq!(items.iter().sum::<Result<_, _>>(), Ok(money("15.29", Currency::usd())));
+        assert_eq!(items.into_iter().sum::<Result<_, _>>(), Ok(money("15.29", Currency::usd())));
+
+        let mixed = vec![money("1", Currency::usd()), money("1", Currency::eur())];
+        assert_eq!(
+            Monetary::sum(&mixed),
+            Err(MoneyError::CurrencyMismatch("USD".to_string(), "EUR".to_string()))
+        );
+        assert_eq!(
+            Monetary::<Decimal>::sum(Vec::<Monetary<Decimal>>::new()),
+            Err(MoneyError::EmptyAggregate("sum".to_string()))
+        );
+
+        let huge = vec![money("1", Currency::usd()).with_amount(Decimal::MAX); 2];
+        assert!(matches!(Monetary::sum(&huge), Err(MoneyError::Overflow(_))));
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        let none: [Monetary<Decimal>; 0] = [];
+        assert_eq!(Monetary::checked_sum(&Currency::eur(), none), Ok(money("0", Currency::eur())));
+        assert_eq!(
+            Monetary::checked_sum(&Currency::usd(), usd(&["1.50", "2.25"])),
+            Ok(money("3.75", Currency::usd()))
+        );
+        assert_eq!(
+            Monetary::checked_sum(&Currency::eur(), usd(&["1.50"])),
+            Err(MoneyError::CurrencyMismatch("EUR".to_string(), "USD".to_string()))
+        );
+
+        // Identity is the code: a different symbol is still the same currency
+        let dollar = Currency::new(CurrencyUnit::new("USD", 840, 2, "US Dollar"), "US$");
+        assert_eq!(
+            Monetary::checked_sum(&dollar, usd(&["1.50", "2.25"])),
+            Ok(money("3.75", Currency::usd()))
+        );
+        let items = vec![money("1.50", dollar.clone()), money("2.25", Currency::usd())];
+        assert_eq!(Monetary::sum(&items), Ok(money("3.75", dollar.clone())));
+        assert_eq!(items[0].checked_add(&items[1]), Ok(money("3.75", dollar)));
+    }
+
+    #[test]
+    fn test_order_statistics() {
+        let items = usd(&["12.00", "3.50", "99.99", "3.50", "40.00"]);
+        assert_eq!(Monetary::min(&items), Ok(money("3.50", Currency::usd())));
+        assert_eq!(Monetary::max(&items), Ok(money("99.99", Currency::usd())));
+        assert_eq!(Monetary::median(&items), Ok(money("12.00", Currency::usd())));
+        assert_eq!(Monetary::average(&items), Ok(money("31.798", Currency::usd())));
+
+        // Even count: mean of the two middle values
+        let even = usd(&["4", "1", "3", "2"]);
+        assert_eq!(Monetary::median(&even), Ok(money("2.5", Currency::usd())));
+        assert_eq!(Monetary::median(usd(&["7"])), Ok(money("7", Currency::usd())));
+
+        let none: Vec<Monetary<Decimal>> = Vec::new();
+        assert_eq!(Monetary::average(&none), Err(MoneyError::EmptyAggregate("average".to_string())));
+        assert_eq!(Monetary::max(&none), Err(MoneyError::EmptyAggregate("max".to_string())));
+        assert_eq!(Monetary::median(&none), Err(MoneyError::EmptyAggregate("median".to_string())));
+
+        let mixed = vec![money("1", Currency::usd()), money("2", Currency::gbp())];
+        assert!(matches!(Monetary::min(&mixed), Err(MoneyError::CurrencyMismatch(..))));
+        assert!(matches!(Monetary::median(&mixed), Err(MoneyError::CurrencyMismatch(..))));
+    }
+
+    #[test]
+    fn test_multi_currency_total() {
+        let items = vec![
+            money("10.00", Currency::usd()),
+            money("5.00", Currency::eur()),
+            money("2.50", Currency::usd()),
+            money("1.00", Currency::eur()),
+        ];
+        let totals = MultiCurrencyTotal::from_amounts(&items).unwrap();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals.get(&Currency::usd()), Some(&money("12.50", Currency::usd())));
+        assert_eq!(totals.get(&Currency::gbp()), None);
+        let codes: Vec<&str> = totals.currencies().map(Currency::code).collect();
+        assert_eq!(codes, ["EUR", "USD"]);
+
+        let mut rates = StaticRateProvider::new();
+        rates.add_rate(&Currency::eur(), &Currency::usd(), Decimal::from_str("1.10").unwrap());
+        let mut conversion = CurrencyConversion::new();
+        conversion.add_provider(Arc::new(rates));
+        let total = totals.collapse(&Currency::usd(), &conversion).unwrap();
+        assert_eq!(total.amount, Decimal::from_str("19.10").unwrap());
+        assert_eq!(total.currency, Currency::usd());
+
+        assert!(totals.collapse(&Currency::gbp(), &conversion).unwrap_err().is_no_rate_found());
+        assert_eq!(
+            totals.collapse_all(&Currency::gbp(), &conversion),
+            Err(vec![ExchangeError::no_rate_found("EUR", "GBP"), ExchangeError::no_rate_found("USD", "GBP")])
+        );
+
+        let mut totals = totals;
+        totals.subtract(&money("12.50", Currency::usd())).unwrap();
+        totals.subtract(&money("3", Currency::chf())).unwrap();
+        assert!(totals.get(&Currency::usd()).unwrap().is_zero());
+        assert_eq!(totals.get(&Currency::chf()), Some(&money("-3", Currency::chf())));
+        assert_eq!(
+            MultiCurrencyTotal::new().collapse(&Currency::gbp(), &conversion),
+            Ok(money("0", Currency::gbp()))
+        );
+    }
+}
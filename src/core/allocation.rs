@@ -0,0 +1,263 @@
+// Allocation
+// ==========
+//
+// Splitting an amount by ratios in the currency's minor units, so the parts always
+// add back up to the original amount. The indivisible remainder (at most one minor
+// unit per part) is handed out according to an `AllocationStrategy`.
+
+use rust_decimal::Decimal;
+use crate::core::{Monetary, Monetizable, MoneyError};
+
+/// How the minor units left over after the proportional split are distributed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AllocationStrategy {
+    /// One extra unit to each of the first parts, in order
+    FirstParts,
+    /// One extra unit to the parts with the largest fractional share (Hamilton's
+    /// method); ties go to the earlier part
+    #[default]
+    LargestRemainder,
+    /// One extra unit to parts picked pseudo-randomly; the same seed always
+    /// produces the same allocation
+    Random { seed: u64 },
+}
+
+impl<T: Monetizable + 'static> Monetary<T> {
+    /// Split the amount proportionally to `ratios` using `AllocationStrategy::LargestRemainder`
+    pub fn allocate(&self, ratios: &[T]) -> Result<Vec<Self>, MoneyError> {
+        self.allocate_with(ratios, AllocationStrategy::default())
+    }
+
+    /// Split the amount proportionally to `ratios`. Parts are expressed in the currency's
+    /// minor units and always sum to the original amount; parts with a zero ratio get nothing.
+    pub fn allocate_with(&self, ratios: &[T], strategy: AllocationStrategy) -> Result<Vec<Self>, MoneyError> {
+        let ratios = ratios
+            .iter()
+            .map(|ratio| ratio.try_to_decimal())
+            .collect::<Result<Vec<_>, _>>()?;
+        let scale = self.minor_unit_scale();
+        let total = to_minor_units(self.amount.try_to_decimal()?, scale)?;
+
+        allocate_units(total, &ratios, strategy)?
+            .into_iter()
+            .map(|units| {
+                let amount = T::try_from_decimal(Decimal::from_i128_with_scale(units, scale))?;
+                Ok(Self::new_with_context(amount, self.currency.clone(), self.context.clone()))
+            })
+            .collect()
+    }
+
+    /// Split the amount into `parts` equal parts, the first parts taking the extra minor units
+    pub fn split(&self, parts: usize) -> Result<Vec<Self>, MoneyError> {
+        self.split_with(parts, AllocationStrategy::FirstParts)
+    }
+
+    /// Split the amount into `parts` equal parts
+    pub fn split_with(&self, parts: usize, strategy: AllocationStrategy) -> Result<Vec<Self>, MoneyError> {
+        if parts == 0 {
+            return Err(MoneyError::InvalidAllocation("cannot split into zero parts".to_string()));
+        }
+        self.allocate_with(&vec![T::try_from_decimal(Decimal::ONE)?; parts], strategy)
+    }
+
+    fn minor_unit_scale(&self) -> u32 {
+        self.currency.precision().clamp(0, 28) as u32
+    }
+}
+
+fn to_minor_units(amount: Decimal, scale: u32) -> Result<i128, MoneyError> {
+    let mut normalized = amount.normalize();
+    if normalized.scale() > scale {
+        return Err(MoneyError::InvalidAllocation(format!(
+            "{} has more than {} decimal places",
+            amount, scale
+        )));
+    }
+    // `rescale` stops short of `scale` when the mantissa can't hold the extra digits
+    normalized.rescale(scale);
+    if normalized.scale() != scale {
+        return Err(MoneyError::Overflow(format!("{} in minor units", amount)));
+    }
+    Ok(normalized.mantissa())
+}
+
+/// Scale the ratios to integers sharing the same exponent
+fn integer_ratios(ratios: &[Decimal]) -> Result<Vec<i128>, MoneyError> {
+    if ratios.is_empty() {
+        return Err(MoneyError::InvalidAllocation("no ratios given".to_string()));
+    }
+    if ratios.iter().any(|ratio| ratio.is_sign_negative() && !ratio.is_zero()) {
+        return Err(MoneyError::InvalidAllocation("ratios must not be negative".to_string()));
+    }
+
+    let scale = ratios.iter().map(|ratio| ratio.normalize().scale()).max().unwrap_or(0);
+    Ok(ratios
+        .iter()
+        .map(|ratio| {
+            let mut ratio = ratio.normalize();
+            ratio.rescale(scale);
+            ratio.mantissa()
+        })
+        .collect())
+}
+
+fn allocate_units(total: i128, ratios: &[Decimal], strategy: AllocationStrategy) -> Result<Vec<i128>, MoneyError> {
+    let ratios = integer_ratios(ratios)?;
+    let ratio_sum = ratios
+        .iter()
+        .try_fold(0i128, |acc, ratio| acc.checked_add(*ratio))
+        .ok_or_else(|| MoneyError::InvalidAllocation("ratios are too large".to_string()))?;
+    if ratio_sum == 0 {
+        return Err(MoneyError::InvalidAllocation("ratios must not all be zero".to_string()));
+    }
+
+    // Allocate the magnitude and restore the sign, so -1.00 / 3 mirrors 1.00 / 3
+    let magnitude = total.unsigned_abs();
+    let ratio_sum = ratio_sum as u128;
+    let mut shares = Vec::with_capacity(ratios.len());
+    let mut remainders = Vec::with_capacity(ratios.len());
+    for ratio in &ratios {
+        let product = magnitude
+            .checked_mul(*ratio as u128)
+            .ok_or_else(|| MoneyError::InvalidAllocation("amount and ratios are too large".to_string()))?;
+        shares.push(product / ratio_sum);
+        remainders.push(product % ratio_sum);
+    }
+
+    let allocated: u128 = shares.iter().sum();
+    let leftover = (magnitude - allocated) as usize;
+    let eligible: Vec<usize> = (0..ratios.len()).filter(|&i| ratios[i] > 0).collect();
+
+    let receivers: Vec<usize> = match strategy {
+        AllocationStrategy::FirstParts => eligible,
+        AllocationStrategy::LargestRemainder => {
+            let mut order = eligible;
+            order.sort_by(|a, b| remainders[*b].cmp(&remainders[*a]).then(a.cmp(b)));
+            order
+        }
+        AllocationStrategy::Random { seed } => {
+            let mut order = eligible;
+            let mut rng = SplitMix64(seed);
+            for i in (1..order.len()).rev() {
+                let j = (rng.next() % (i as u64 + 1)) as usize;
+                order.swap(i, j);
+            }
+            order
+        }
+    };
+
+    // `leftover` is below the number of non-zero ratios, so every receiver gets at most one unit
+    for &index in receivers.iter().take(leftover) {
+        shares[index] += 1;
+    }
+
+    Ok(shares
+        .into_iter()
+        .map(|share| if total < 0 { -(share as i128) } else { share as i128 })
+        .collect())
+}
+
+/// Small deterministic generator so seeded allocations are reproducible everywhere
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use crate::core::currency::Currency;
+
+    fn usd(value: &str) -> Monetary<Decimal> {
+        Monetary::new(Decimal::from_str(value).unwrap(), Currency::usd())
+    }
+
+    fn amounts(parts: &[Monetary<Decimal>]) -> Vec<String> {
+        parts.iter().map(|part| part.amount.to_string()).collect()
+    }
+
+    fn sum(parts: &[Monetary<Decimal>]) -> Decimal {
+        parts.iter().map(|part| part.amount).sum()
+    }
+
+    #[test]
+    fn test_split_keeps_every_cent() {
+        let parts = usd("100").split(3).unwrap();
+        assert_eq!(amounts(&parts), ["33.34", "33.33", "33.33"]);
+        assert_eq!(sum(&parts), Decimal::from(100));
+
+        let parts = usd("-0.05").split(2).unwrap();
+        assert_eq!(amounts(&parts), ["-0.03", "-0.02"]);
+
+        let yen = Monetary::new(Decimal::from(1000), Currency::jpy()).split(3).unwrap();
+        assert_eq!(amounts(&yen), ["334", "333", "333"]);
+    }
+
+    #[test]
+    fn test_allocate_strategies() {
+        let ratios = [Decimal::from(1), Decimal::from(4), Decimal::from(1)];
+        let amount = usd("0.05");
+
+        // Exact shares are 0.83, 3.33 and 0.83 cents; two cents are left over
+        let first = amount.allocate_with(&ratios, AllocationStrategy::FirstParts).unwrap();
+        assert_eq!(amounts(&first), ["0.01", "0.04", "0.00"]);
+
+        let largest = amount.allocate_with(&ratios, AllocationStrategy::LargestRemainder).unwrap();
+        assert_eq!(amounts(&largest), ["0.01", "0.03", "0.01"]);
+
+        let ratios = [Decimal::from_str("0.7").unwrap(), Decimal::from_str("0.3").unwrap()];
+        let parts = usd("0.05").allocate(&ratios).unwrap();
+        assert_eq!(amounts(&parts), ["0.04", "0.01"]);
+        let parts = usd("0.05").allocate_with(&ratios, AllocationStrategy::FirstParts).unwrap();
+        assert_eq!(amounts(&parts), ["0.04", "0.01"]);
+
+        let parts = usd("10").allocate(&[Decimal::from(0), Decimal::from(3)]).unwrap();
+        assert_eq!(amounts(&parts), ["0.00", "10.00"]);
+    }
+
+    #[test]
+    fn test_random_strategy_is_seeded() {
+        let amount = usd("1.00");
+        let strategy = AllocationStrategy::Random { seed: 42 };
+        let parts = amount.split_with(7, strategy).unwrap();
+
+        assert_eq!(parts, amount.split_with(7, strategy).unwrap());
+        assert_eq!(sum(&parts), Decimal::ONE);
+        assert!(parts.iter().all(|part| part.amount == Decimal::from_str("0.14").unwrap()
+            || part.amount == Decimal::from_str("0.15").unwrap()));
+    }
+
+    #[test]
+    fn test_allocation_errors() {
+        assert!(matches!(usd("10").split(0), Err(MoneyError::InvalidAllocation(_))));
+        assert!(matches!(usd("10").allocate(&[]), Err(MoneyError::InvalidAllocation(_))));
+        assert!(matches!(usd("10").allocate(&[Decimal::ZERO]), Err(MoneyError::InvalidAllocation(_))));
+        assert!(matches!(usd("10").allocate(&[Decimal::NEGATIVE_ONE, Decimal::TWO]), Err(MoneyError::InvalidAllocation(_))));
+        assert!(matches!(usd("10.005").split(2), Err(MoneyError::InvalidAllocation(_))));
+    }
+
+    #[test]
+    fn test_split_huge_amount() {
+        let huge = Monetary::new(Decimal::MAX, Currency::usd());
+        assert!(matches!(huge.split(2), Err(MoneyError::Overflow(_))));
+
+        // Still fits with two extra digits, and the parts add back up exactly
+        let large = usd("792281625142643375935439503.35");
+        let parts = large.split(2).unwrap();
+        assert_eq!(sum(&parts), large.amount);
+    }
+
+    #[test]
+    fn test_split_f64_amount() {
+        let parts = Monetary::new(100.10f64, Currency::usd()).split(3).unwrap();
+        assert_eq!(parts.iter().map(|p| p.amount).collect::<Vec<_>>(), [33.37, 33.37, 33.36]);
+    }
+}
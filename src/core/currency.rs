@@ -1,26 +1,46 @@
+use std::borrow::Cow;
 use std::fmt;
-use std::ops::{Add, Sub, Mul, Div};
 use std::str::FromStr;
-use std::collections::HashMap;
-use std::sync::OnceLock;
-use crate::core::CurrencyUnit; // Assuming CurrencyUnit is defined elsewhere in your crate
-use crate::errors::CurrencyError; // Assuming CurrencyError is defined elsewhere
+use chrono::{NaiveDate, Utc};
+use crate::core::CurrencyUnit;
+use crate::core::currency_id::CurrencyId;
+use crate::core::currency_table::currency_table;
+use crate::core::iso4217;
+use crate::core::registry::CurrencyRegistry;
+use crate::errors::CurrencyError;
 
 // Currency representation - wrapper around CurrencyUnit with display logic
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Currency {
     unit: CurrencyUnit,
-    symbol: String,
+    symbol: Cow<'static, str>,
+    withdrawn_on: Option<NaiveDate>,
 }
 
 impl Currency {
     pub fn new(unit: CurrencyUnit, symbol: &str) -> Self {
         Self {
             unit,
-            symbol: symbol.to_string(),
+            symbol: Cow::Owned(symbol.to_string()),
+            withdrawn_on: None,
         }
     }
 
+    /// Const constructor for currencies backed by static data, such as `Currency::USD`
+    pub const fn from_static(unit: CurrencyUnit, symbol: &'static str) -> Self {
+        Self {
+            unit,
+            symbol: Cow::Borrowed(symbol),
+            withdrawn_on: None,
+        }
+    }
+
+    /// Mark the currency as withdrawn from circulation on `date`
+    pub const fn withdrawn(mut self, date: NaiveDate) -> Self {
+        self.withdrawn_on = Some(date);
+        self
+    }
+
     // Delegate core properties to the underlying unit
     pub fn code(&self) -> &str {
         self.unit.get_code()
@@ -30,6 +50,11 @@ impl Currency {
         self.unit.get_numeric_code()
     }
 
+    /// Unique identity used for comparisons and rate lookups
+    pub fn id(&self) -> CurrencyId {
+        CurrencyId::new(self.code())
+    }
+
     pub fn precision(&self) -> i32 {
         self.unit.get_default_fraction_digits()
     }
@@ -46,9 +71,24 @@ impl Currency {
         &self.unit
     }
 
-    // Create from ISO code - looks up from registry
+    /// Date the currency stopped being legal tender, if it has been withdrawn
+    pub fn withdrawn_on(&self) -> Option<NaiveDate> {
+        self.withdrawn_on
+    }
+
+    /// Check whether the currency is still in circulation today
+    pub fn is_active(&self) -> bool {
+        self.is_active_on(Utc::now().date_naive())
+    }
+
+    /// Check whether the currency was in circulation on `date`
+    pub fn is_active_on(&self, date: NaiveDate) -> bool {
+        self.withdrawn_on.is_none_or(|withdrawn| date < withdrawn)
+    }
+
+    // Create from code - looks up from the global registry
     pub fn from_code(code: &str) -> Option<Self> {
-        get_currency_registry().get(&code.to_uppercase() as &str).cloned()
+        CurrencyRegistry::global().get(code)
     }
 
     // Create currency with custom symbol (override default)
@@ -61,360 +101,75 @@ impl Currency {
     }
 }
 
-// Common currencies - static constructors
-impl Currency {
-    pub fn usd() -> Self {
-        let unit = CurrencyUnit::new("USD", 840, 2, "US Dollar");
-        Self::new(unit, "$")
-    }
-
-    pub fn eur() -> Self {
-        let unit = CurrencyUnit::new("EUR", 978, 2, "Euro");
-        Self::new(unit, "€")
-    }
-
-    pub fn gbp() -> Self {
-        let unit = CurrencyUnit::new("GBP", 826, 2, "British Pound Sterling");
-        Self::new(unit, "£")
-    }
-
-    pub fn jpy() -> Self {
-        let unit = CurrencyUnit::new("JPY", 392, 0, "Japanese Yen");
-        Self::new(unit, "¥")
-    }
-
-    pub fn chf() -> Self {
-        let unit = CurrencyUnit::new("CHF", 756, 2, "Swiss Franc");
-        Self::new(unit, "Fr")
-    }
-
-    pub fn cad() -> Self {
-        let unit = CurrencyUnit::new("CAD", 124, 2, "Canadian Dollar");
-        Self::new(unit, "C$")
-    }
-
-    pub fn aud() -> Self {
-        let unit = CurrencyUnit::new("AUD", 36, 2, "Australian Dollar");
-        Self::new(unit, "A$")
-    }
-
-    pub fn cny() -> Self {
-        let unit = CurrencyUnit::new("CNY", 156, 2, "Chinese Yuan");
-        Self::new(unit, "¥")
-    }
-
-    pub fn inr() -> Self {
-        let unit = CurrencyUnit::new("INR", 356, 2, "Indian Rupee");
-        Self::new(unit, "₹")
-    }
-
-    pub fn krw() -> Self {
-        let unit = CurrencyUnit::new("KRW", 410, 0, "South Korean Won");
-        Self::new(unit, "₩")
-    }
-
-    pub fn brl() -> Self {
-        let unit = CurrencyUnit::new("BRL", 986, 2, "Brazilian Real");
-        Self::new(unit, "R$")
-    }
-
-    pub fn rub() -> Self {
-        let unit = CurrencyUnit::new("RUB", 643, 2, "Russian Ruble");
-        Self::new(unit, "₽")
-    }
-
-    pub fn zar() -> Self {
-        let unit = CurrencyUnit::new("ZAR", 710, 2, "South African Rand");
-        Self::new(unit, "R")
-    }
-
-    pub fn mxn() -> Self {
-        let unit = CurrencyUnit::new("MXN", 484, 2, "Mexican Peso");
-        Self::new(unit, "$")
-    }
-
-    pub fn sgd() -> Self {
-        let unit = CurrencyUnit::new("SGD", 702, 2, "Singapore Dollar");
-        Self::new(unit, "S$")
-    }
-
-    pub fn nzd() -> Self {
-        let unit = CurrencyUnit::new("NZD", 554, 2, "New Zealand Dollar");
-        Self::new(unit, "NZ$")
-    }
-
-    pub fn hkd() -> Self {
-        let unit = CurrencyUnit::new("HKD", 344, 2, "Hong Kong Dollar");
-        Self::new(unit, "HK$")
-    }
-
-    pub fn thb() -> Self {
-        let unit = CurrencyUnit::new("THB", 764, 2, "Thai Baht");
-        Self::new(unit, "฿")
-    }
-
-    pub fn php() -> Self {
-        let unit = CurrencyUnit::new("PHP", 608, 2, "Philippine Peso");
-        Self::new(unit, "₱")
-    }
-
-    pub fn myr() -> Self {
-        let unit = CurrencyUnit::new("MYR", 458, 2, "Malaysian Ringgit");
-        Self::new(unit, "RM")
-    }
-
-    pub fn idr() -> Self {
-        let unit = CurrencyUnit::new("IDR", 360, 2, "Indonesian Rupiah");
-        Self::new(unit, "Rp")
-    }
-
-    pub fn egp() -> Self {
-        let unit = CurrencyUnit::new("EGP", 818, 2, "Egyptian Pound");
-        Self::new(unit, "E£")
-    }
-
-    pub fn clp() -> Self {
-        let unit = CurrencyUnit::new("CLP", 152, 0, "Chilean Peso");
-        Self::new(unit, "CLP$")
-    }
-
-    pub fn nok() -> Self {
-        let unit = CurrencyUnit::new("NOK", 578, 2, "Norwegian Krone");
-        Self::new(unit, "kr")
-    }
-
-    pub fn sek() -> Self {
-        let unit = CurrencyUnit::new("SEK", 752, 2, "Swedish Krona");
-        Self::new(unit, "kr")
-    }
-
-    pub fn dkk() -> Self {
-        let unit = CurrencyUnit::new("DKK", 208, 2, "Danish Krone");
-        Self::new(unit, "kr")
-    }
-
-    pub fn pln() -> Self {
-        let unit = CurrencyUnit::new("PLN", 985, 2, "Polish Zloty");
-        Self::new(unit, "zł")
-    }
-
-    pub fn czk() -> Self {
-        let unit = CurrencyUnit::new("CZK", 203, 2, "Czech Koruna");
-        Self::new(unit, "Kč")
-    }
-
-    pub fn huf() -> Self {
-        let unit = CurrencyUnit::new("HUF", 348, 2, "Hungarian Forint");
-        Self::new(unit, "Ft")
-    }
-
-    pub fn isk() -> Self {
-        let unit = CurrencyUnit::new("ISK", 352, 0, "Icelandic Króna");
-        Self::new(unit, "kr")
-    }
+// Named currencies: `Currency::USD` constants and `Currency::usd()` constructors,
+// generated from the currency table
+macro_rules! currency_constants {
+    ($($variant:ident $ctor:ident: $kind:ident $source:tt),* $(,)?) => {
+        impl Currency {
+            $(
+                pub const $variant: Currency = named_currency!(stringify!($variant), $source);
+            )*
+
+            $(
+                pub fn $ctor() -> Self {
+                    Self::$variant
+                }
+            )*
+        }
 
-    pub fn ron() -> Self {
-        let unit = CurrencyUnit::new("RON", 946, 2, "Romanian Leu");
-        Self::new(unit, "lei")
-    }
+        /// Every currency in the named table
+        pub(crate) const NAMED_CURRENCIES: &[Currency] = &[$(Currency::$variant),*];
 
-    pub fn hrk() -> Self {
-        let unit = CurrencyUnit::new("HRK", 191, 2, "Croatian Kuna"); // Croatia adopted EUR in 2023, but keeping for historical context
-        Self::new(unit, "kn")
-    }
+        /// Kind of a named currency, by upper-case code
+        fn named_kind(code: &str) -> Option<NamedKind> {
+            match code {
+                $(stringify!($variant) => Some(NamedKind::$kind),)*
+                _ => None,
+            }
+        }
+    };
+}
 
-    pub fn ils() -> Self {
-        let unit = CurrencyUnit::new("ILS", 376, 2, "Israeli New Shekel");
-        Self::new(unit, "₪")
-    }
+/// Kind column of the currency table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NamedKind {
+    Fiat,
+    Crypto,
+    Metal,
+}
 
-    pub fn aed() -> Self {
-        let unit = CurrencyUnit::new("AED", 784, 2, "UAE Dirham");
-        Self::new(unit, "د.إ")
-    }
+macro_rules! named_currency {
+    ($code:expr, iso) => {
+        iso4217::currency($code)
+    };
+    ($code:expr, ($numeric:expr, $minor:expr, $name:expr, $symbol:expr)) => {
+        Currency::from_static(CurrencyUnit::from_static($code, $numeric, $minor, $name), $symbol)
+    };
+}
 
-    pub fn sar() -> Self {
-        let unit = CurrencyUnit::new("SAR", 682, 2, "Saudi Riyal");
-        Self::new(unit, "﷼")
-    }
+currency_table!(currency_constants);
 
+impl Currency {
+    /// Same as `Currency::r#try()`, for callers that would rather avoid the raw identifier
     pub fn try_currency() -> Self {
-        let unit = CurrencyUnit::new("TRY", 949, 2, "Turkish Lira");
-        Self::new(unit, "₺")
-    }
-
-    pub fn kwd() -> Self {
-        let unit = CurrencyUnit::new("KWD", 414, 3, "Kuwaiti Dinar");
-        Self::new(unit, "KD")
-    }
-
-    pub fn qar() -> Self {
-        let unit = CurrencyUnit::new("QAR", 634, 2, "Qatari Riyal");
-        Self::new(unit, "QR")
-    }
-
-    pub fn mad() -> Self {
-        let unit = CurrencyUnit::new("MAD", 504, 2, "Moroccan Dirham");
-        Self::new(unit, "د.م.")
-    }
-
-    pub fn ngn() -> Self {
-        let unit = CurrencyUnit::new("NGN", 566, 2, "Nigerian Naira");
-        Self::new(unit, "₦")
-    }
-
-    // Cryptocurrencies (non-ISO codes, numeric code 0 often used for non-ISO)
-    pub fn btc() -> Self {
-        let unit = CurrencyUnit::new("BTC", 0, 8, "Bitcoin");
-        Self::new(unit, "₿")
-    }
-
-    pub fn eth() -> Self {
-        let unit = CurrencyUnit::new("ETH", 0, 18, "Ethereum"); // Ethereum typically uses 18 decimal places (wei)
-        Self::new(unit, "Ξ")
-    }
-
-    pub fn ltc() -> Self {
-        let unit = CurrencyUnit::new("LTC", 0, 8, "Litecoin");
-        Self::new(unit, "Ł")
-    }
-
-    pub fn xrp() -> Self {
-        let unit = CurrencyUnit::new("XRP", 0, 6, "Ripple"); // XRP uses 6 decimal places
-        Self::new(unit, "✕")
-    }
-
-    pub fn ada() -> Self {
-        let unit = CurrencyUnit::new("ADA", 0, 6, "Cardano"); // ADA typically uses 6 decimal places
-        Self::new(unit, "₳")
-    }
-
-    pub fn doge() -> Self {
-        let unit = CurrencyUnit::new("DOGE", 0, 8, "Dogecoin");
-        Self::new(unit, "Ð")
-    }
-
-    pub fn dot() -> Self {
-        let unit = CurrencyUnit::new("DOT", 0, 10, "Polkadot"); // DOT typically uses 10 decimal places
-        Self::new(unit, "●")
-    }
-
-    pub fn sol() -> Self {
-        let unit = CurrencyUnit::new("SOL", 0, 9, "Solana"); // SOL typically uses 9 decimal places
-        Self::new(unit, "◎")
-    }
-
-    pub fn usdt() -> Self {
-        let unit = CurrencyUnit::new("USDT", 0, 6, "Tether USD"); // USDT commonly uses 6 decimal places
-        Self::new(unit, "$")
-    }
-
-    pub fn usdc() -> Self {
-        let unit = CurrencyUnit::new("USDC", 0, 6, "USD Coin"); // USDC commonly uses 6 decimal places
-        Self::new(unit, "$")
-    }
-
-    // Precious metals (XAU, XAG, XPT, XPD are ISO codes)
-    pub fn xau() -> Self {
-        let unit = CurrencyUnit::new("XAU", 959, 4, "Gold (troy ounce)");
-        Self::new(unit, "Au")
-    }
-
-    pub fn xag() -> Self {
-        let unit = CurrencyUnit::new("XAG", 961, 4, "Silver (troy ounce)");
-        Self::new(unit, "Ag")
-    }
-
-    pub fn xpt() -> Self {
-        let unit = CurrencyUnit::new("XPT", 962, 4, "Platinum (troy ounce)");
-        Self::new(unit, "Pt")
-    }
-
-    pub fn xpd() -> Self {
-        let unit = CurrencyUnit::new("XPD", 964, 4, "Palladium (troy ounce)");
-        Self::new(unit, "Pd")
-    }
-
-    pub fn xrh() -> Self {
-        let unit = CurrencyUnit::new("XRH", 0, 4, "Rhodium (troy ounce)"); // ISO code for Rhodium is not standard, using 0 for numeric code
-        Self::new(unit, "Rh")
+        Self::TRY
     }
 }
 
-// Global currency registry for lookup by code
-static CURRENCY_REGISTRY: OnceLock<HashMap<&'static str, Currency>> = OnceLock::new();
-
-fn get_currency_registry() -> &'static HashMap<&'static str, Currency> {
-    CURRENCY_REGISTRY.get_or_init(|| {
-        let mut registry = HashMap::new();
-
-        // Major Fiat Currencies
-        registry.insert("USD", Currency::usd());
-        registry.insert("EUR", Currency::eur());
-        registry.insert("GBP", Currency::gbp());
-        registry.insert("JPY", Currency::jpy());
-        registry.insert("CHF", Currency::chf());
-        registry.insert("CAD", Currency::cad());
-        registry.insert("AUD", Currency::aud());
-        registry.insert("CNY", Currency::cny());
-        registry.insert("INR", Currency::inr());
-        registry.insert("KRW", Currency::krw());
-        registry.insert("BRL", Currency::brl());
-        registry.insert("RUB", Currency::rub());
-        registry.insert("ZAR", Currency::zar());
-        registry.insert("MXN", Currency::mxn());
-        registry.insert("SGD", Currency::sgd());
-        registry.insert("NZD", Currency::nzd());
-        registry.insert("HKD", Currency::hkd());
-        registry.insert("THB", Currency::thb());
-        registry.insert("PHP", Currency::php());
-        registry.insert("MYR", Currency::myr());
-        registry.insert("IDR", Currency::idr());
-        registry.insert("EGP", Currency::egp());
-        registry.insert("CLP", Currency::clp());
-
-        // European Currencies
-        registry.insert("NOK", Currency::nok());
-        registry.insert("SEK", Currency::sek());
-        registry.insert("DKK", Currency::dkk());
-        registry.insert("PLN", Currency::pln());
-        registry.insert("CZK", Currency::czk());
-        registry.insert("HUF", Currency::huf());
-        registry.insert("ISK", Currency::isk());
-        registry.insert("RON", Currency::ron());
-        registry.insert("HRK", Currency::hrk());
-
-        // Middle East / Africa
-        registry.insert("ILS", Currency::ils());
-        registry.insert("AED", Currency::aed());
-        registry.insert("SAR", Currency::sar());
-        registry.insert("TRY", Currency::try_currency()); // Renamed to avoid keyword conflict
-        registry.insert("KWD", Currency::kwd());
-        registry.insert("QAR", Currency::qar());
-        registry.insert("MAD", Currency::mad());
-        registry.insert("NGN", Currency::ngn());
-
-        // Cryptocurrencies
-        registry.insert("BTC", Currency::btc());
-        registry.insert("ETH", Currency::eth());
-        registry.insert("LTC", Currency::ltc());
-        registry.insert("XRP", Currency::xrp());
-        registry.insert("ADA", Currency::ada());
-        registry.insert("DOGE", Currency::doge());
-        registry.insert("DOT", Currency::dot());
-        registry.insert("SOL", Currency::sol());
-        registry.insert("USDT", Currency::usdt());
-        registry.insert("USDC", Currency::usdc());
-
-        // Precious Metals
-        registry.insert("XAU", Currency::xau());
-        registry.insert("XAG", Currency::xag());
-        registry.insert("XPT", Currency::xpt());
-        registry.insert("XPD", Currency::xpd());
-        registry.insert("XRH", Currency::xrh());
-
-        registry
-    })
+/// Currencies every registry created with `CurrencyRegistry::with_defaults` starts with:
+/// the ISO 4217 dataset (active and withdrawn) plus the named currencies outside it
+/// (crypto assets and rhodium)
+pub(crate) fn builtin_currencies() -> Vec<Currency> {
+    let mut currencies = iso4217::active_currencies();
+    currencies.extend(iso4217::historical_currencies());
+    currencies.extend(
+        NAMED_CURRENCIES
+            .iter()
+            .filter(|named| !currencies.iter().any(|iso| iso.code() == named.code()))
+            .cloned()
+            .collect::<Vec<_>>(),
+    );
+    currencies
 }
 
 // Display formatting
@@ -435,39 +190,34 @@ impl FromStr for Currency {
 
 // Utility functions
 impl Currency {
-    /// Get all available currencies
-    pub fn available_currencies() -> Vec<&'static Currency> {
-        get_currency_registry().values().collect()
+    /// Get all currencies in the global registry
+    pub fn available_currencies() -> Vec<Currency> {
+        CurrencyRegistry::global().currencies()
     }
 
-    /// Check if a currency code is supported
+    /// Check if a currency code is registered globally
     pub fn is_supported(code: &str) -> bool {
-        get_currency_registry().contains_key(code.to_uppercase().as_str())
+        CurrencyRegistry::global().contains(code)
     }
 
-    /// Get currency by numeric code
+    /// Get currency by numeric code. 0 means "no ISO numeric code" and never matches.
     pub fn from_numeric_code(numeric_code: i32) -> Option<Self> {
-        get_currency_registry()
-            .values()
-            .find(|currency| currency.numeric_code() == numeric_code)
-            .cloned()
+        CurrencyRegistry::global().get_by_numeric(numeric_code)
     }
 
-    /// Compare currencies (by numeric code for performance)
+    /// Compare currency identity, ignoring display details such as the symbol
     pub fn same_currency(&self, other: &Currency) -> bool {
-        self.numeric_code() == other.numeric_code()
+        self.id() == other.id()
     }
 
-    /// Check if this is a cryptocurrency
+    /// Check if this is a cryptocurrency, per the kind column of the currency table
     pub fn is_cryptocurrency(&self) -> bool {
-        // Updated to include all cryptocurrencies
-        matches!(self.code(), "BTC" | "ETH" | "LTC" | "XRP" | "ADA" | "DOGE" | "DOT" | "SOL" | "USDT" | "USDC")
+        named_kind(self.code()) == Some(NamedKind::Crypto)
     }
 
-    /// Check if this is a precious metal
+    /// Check if this is a precious metal, per the kind column of the currency table
     pub fn is_precious_metal(&self) -> bool {
-        // Updated to include all precious metals
-        matches!(self.code(), "XAU" | "XAG" | "XPT" | "XPD" | "XRH")
+        named_kind(self.code()) == Some(NamedKind::Metal)
     }
 
     /// Check if this is fiat currency
@@ -488,44 +238,6 @@ impl Currency {
 #[cfg(test)]
 mod tests {
     use super::*;
-    // Mock CurrencyUnit and CurrencyError for testing purposes
-    // In a real project, these would be defined in their respective modules.
-    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-    pub struct CurrencyUnit {
-        code: String,
-        numeric_code: i32,
-        default_fraction_digits: i32,
-        display_name: String,
-    }
-
-    impl CurrencyUnit {
-        pub fn new(code: &str, numeric_code: i32, default_fraction_digits: i32, display_name: &str) -> Self {
-            Self {
-                code: code.to_string(),
-                numeric_code,
-                default_fraction_digits,
-                display_name: display_name.to_string(),
-            }
-        }
-        pub fn get_code(&self) -> &str { &self.code }
-        pub fn get_numeric_code(&self) -> i32 { self.numeric_code }
-        pub fn get_default_fraction_digits(&self) -> i32 { self.default_fraction_digits }
-        pub fn get_display_name(&self) -> &str { &self.display_name }
-    }
-
-    #[derive(Debug, PartialEq)]
-    pub enum CurrencyError {
-        UnknownCurrency(String),
-        ConversionError(String),
-        // Add other error types as needed
-    }
-
-    impl CurrencyError {
-        pub fn unknown_currency<T: Into<String>>(currency_code: T) -> Self {
-            CurrencyError::UnknownCurrency(currency_code.into())
-        }
-    }
-
 
     #[test]
     fn test_currency_creation() {
@@ -588,6 +300,17 @@ mod tests {
         assert!(!xau.is_fiat());
         assert!(!xau.is_cryptocurrency());
         assert!(xpt.is_precious_metal());
+
+        // Non-ISO rows are not all crypto: rhodium is a metal
+        let xrh = Currency::xrh();
+        assert!(xrh.is_precious_metal());
+        assert!(!xrh.is_cryptocurrency());
+        let crypto: Vec<&str> = NAMED_CURRENCIES
+            .iter()
+            .filter(|c| c.is_cryptocurrency())
+            .map(|c| c.code())
+            .collect();
+        assert_eq!(crypto, ["BTC", "ETH", "LTC", "XRP", "ADA", "DOGE", "DOT", "SOL", "USDT", "USDC"]);
     }
 
     #[test]
@@ -638,6 +361,37 @@ mod tests {
         assert!(all_currencies.iter().any(|c| c.code() == "XPT"));
     }
 
+    #[test]
+    fn test_named_constructors_match_registry() {
+        for currency in NAMED_CURRENCIES {
+            assert_eq!(Currency::from_code(currency.code()).as_ref(), Some(currency));
+        }
+        assert_eq!(NAMED_CURRENCIES.len(), 55);
+        assert_eq!(Currency::usd(), Currency::USD);
+        assert_eq!(Currency::try_currency(), Currency::r#try());
+        assert_eq!(Currency::HRK.withdrawn_on(), NaiveDate::from_ymd_opt(2023, 1, 1));
+        assert_eq!(Currency::ETH.precision(), 18);
+        assert_eq!(Currency::XRH.display_name(), "Rhodium (troy ounce)");
+    }
+
+    #[test]
+    fn test_historical_currencies() {
+        let hrk = Currency::from_code("HRK").unwrap();
+        assert!(!hrk.is_active());
+        assert_eq!(hrk.withdrawn_on(), NaiveDate::from_ymd_opt(2023, 1, 1));
+        assert!(hrk.is_active_on(NaiveDate::from_ymd_opt(2022, 12, 31).unwrap()));
+
+        let dem = Currency::from_code("DEM").unwrap();
+        assert_eq!(dem.numeric_code(), 276);
+        assert!(!dem.is_active());
+
+        assert!(Currency::from_code("BHD").unwrap().is_active());
+        assert_eq!(Currency::from_code("CLF").unwrap().precision(), 4);
+
+        // ANG and its successor XCG share numeric code 532
+        assert_eq!(Currency::from_numeric_code(532).unwrap().code(), "XCG");
+    }
+
     #[test]
     fn test_get_unit() {
         let usd_currency = Currency::usd();
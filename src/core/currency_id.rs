@@ -0,0 +1,85 @@
+use std::fmt;
+
+/// Compact, `Copy` identity of a currency derived from its alpha code.
+///
+/// Numeric codes are not unique: every crypto asset and other non-ISO unit uses 0.
+/// Alpha codes are, so the id packs the uppercase code into a `u128` (ASCII codes of up
+/// to 16 bytes). Longer or non-ASCII codes fall back to a 64-bit FNV-1a hash tagged with
+/// the top bit, which packed ASCII codes never set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CurrencyId(u128);
+
+const HASHED_TAG: u128 = 1 << 127;
+
+impl CurrencyId {
+    pub fn new(code: &str) -> Self {
+        let bytes = code.as_bytes();
+        if bytes.len() <= 16 && bytes.is_ascii() {
+            let packed = bytes
+                .iter()
+                .fold(0u128, |acc, b| (acc << 8) | b.to_ascii_uppercase() as u128);
+            Self(packed)
+        } else {
+            let hash = bytes.iter().fold(0xcbf2_9ce4_8422_2325u64, |acc, b| {
+                (acc ^ b.to_ascii_uppercase() as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+            Self(HASHED_TAG | hash as u128)
+        }
+    }
+
+    /// Raw packed value, stable across runs
+    pub fn as_u128(&self) -> u128 {
+        self.0
+    }
+
+    /// The alpha code, when it was short enough to be packed
+    pub fn code(&self) -> Option<String> {
+        if self.0 & HASHED_TAG != 0 {
+            return None;
+        }
+        let bytes: Vec<u8> = self.0.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+        String::from_utf8(bytes).ok()
+    }
+}
+
+impl From<&str> for CurrencyId {
+    fn from(code: &str) -> Self {
+        Self::new(code)
+    }
+}
+
+impl fmt::Display for CurrencyId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.code() {
+            Some(code) => write!(f, "{}", code),
+            None => write!(f, "#{:032x}", self.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identity_from_code() {
+        assert_eq!(CurrencyId::new("usd"), CurrencyId::new("USD"));
+        assert_ne!(CurrencyId::new("BTC"), CurrencyId::new("ETH"));
+        assert_ne!(CurrencyId::new("USD"), CurrencyId::new("USDT"));
+        assert_eq!(CurrencyId::new("DOGE").code().as_deref(), Some("DOGE"));
+        assert_eq!(CurrencyId::new("USD").to_string(), "USD");
+
+        let long = CurrencyId::new("INTERNALSETTLEMENTUNIT");
+        assert_eq!(long, CurrencyId::new("internalsettlementunit"));
+        assert_eq!(long.code(), None);
+        assert_ne!(long, CurrencyId::new("INTERNALSETTLEMENTUNIT2"));
+
+        // A leading non-ASCII byte would set the tag bit when packed, so such codes are hashed
+        for code in ["€", "éABCDEFGHIJKLMN"] {
+            let id = CurrencyId::new(code);
+            assert_eq!(id.as_u128() >> 64, HASHED_TAG >> 64);
+            assert_eq!(id.code(), None);
+            assert_eq!(id, CurrencyId::new(code));
+        }
+    }
+}
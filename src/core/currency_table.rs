@@ -0,0 +1,86 @@
+// Named currencies
+// ===============
+//
+// The one list of currencies that get a `Money` variant, a `Money::usd`-style helper,
+// a `Currency::usd()` constructor and a `Currency::USD` constant. Adding a currency is
+// one row here; everything else is generated by the macros that consume this table.
+//
+// Each row is `VARIANT constructor: Kind source`, where the variant name is also the
+// currency code and `Kind` is `Fiat`, `Crypto` or `Metal`. `iso` takes the numeric
+// code, minor units, name and symbol from the ISO 4217 dataset (a code missing from it
+// fails to compile); non-ISO codes give `(numeric code, minor units, name, symbol)`
+// inline.
+
+/// Expand `$generate! { ... }` with every row of the named currency table
+macro_rules! currency_table {
+    ($generate:ident) => {
+        $generate! {
+            // Major fiat currencies
+            USD usd: Fiat iso,
+            EUR eur: Fiat iso,
+            GBP gbp: Fiat iso,
+            JPY jpy: Fiat iso,
+            CHF chf: Fiat iso,
+            CAD cad: Fiat iso,
+            AUD aud: Fiat iso,
+            CNY cny: Fiat iso,
+            INR inr: Fiat iso,
+            KRW krw: Fiat iso,
+            BRL brl: Fiat iso,
+            RUB rub: Fiat iso,
+            ZAR zar: Fiat iso,
+            MXN mxn: Fiat iso,
+            SGD sgd: Fiat iso,
+            NZD nzd: Fiat iso,
+            HKD hkd: Fiat iso,
+            THB thb: Fiat iso,
+            PHP php: Fiat iso,
+            MYR myr: Fiat iso,
+            IDR idr: Fiat iso,
+            EGP egp: Fiat iso,
+            CLP clp: Fiat iso,
+
+            // European currencies (HRK was replaced by EUR on 2023-01-01)
+            NOK nok: Fiat iso,
+            SEK sek: Fiat iso,
+            DKK dkk: Fiat iso,
+            PLN pln: Fiat iso,
+            CZK czk: Fiat iso,
+            HUF huf: Fiat iso,
+            ISK isk: Fiat iso,
+            RON ron: Fiat iso,
+            HRK hrk: Fiat iso,
+
+            // Middle East / Africa
+            ILS ils: Fiat iso,
+            AED aed: Fiat iso,
+            SAR sar: Fiat iso,
+            TRY r#try: Fiat iso,
+            KWD kwd: Fiat iso,
+            QAR qar: Fiat iso,
+            MAD mad: Fiat iso,
+            NGN ngn: Fiat iso,
+
+            // Cryptocurrencies (no ISO numeric code)
+            BTC btc: Crypto (0, 8, "Bitcoin", "₿"),
+            ETH eth: Crypto (0, 18, "Ethereum", "Ξ"),
+            LTC ltc: Crypto (0, 8, "Litecoin", "Ł"),
+            XRP xrp: Crypto (0, 6, "Ripple", "✕"),
+            ADA ada: Crypto (0, 6, "Cardano", "₳"),
+            DOGE doge: Crypto (0, 8, "Dogecoin", "Ð"),
+            DOT dot: Crypto (0, 10, "Polkadot", "●"),
+            SOL sol: Crypto (0, 9, "Solana", "◎"),
+            USDT usdt: Crypto (0, 6, "Tether USD", "$"),
+            USDC usdc: Crypto (0, 6, "USD Coin", "$"),
+
+            // Precious metals, in troy ounces (rhodium has no ISO code)
+            XAU xau: Metal iso,
+            XAG xag: Metal iso,
+            XPT xpt: Metal iso,
+            XPD xpd: Metal iso,
+            XRH xrh: Metal (0, 4, "Rhodium (troy ounce)", "Rh"),
+        }
+    };
+}
+
+pub(crate) use currency_table;
@@ -1,22 +1,19 @@
 // Rust Monetary API Implementation
 // Inspired by JSR 354 (Java Monetary API)
 
-use std::cmp::{Ordering, PartialEq, PartialOrd};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::cmp::PartialEq;
 use std::fmt;
-use std::ops::{Add, Div, Mul, Sub};
-use std::str::FromStr;
-use std::sync::Arc;
 
 // ===== Currency =====
 
 /// Represents a currency, similar to javax.money.CurrencyUnit
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CurrencyUnit {
-    code: String,
+    code: Cow<'static, str>,
     numeric_code: i32,
     default_fraction_digits: i32,
-    display_name: String,
+    display_name: Cow<'static, str>,
 }
 
 impl CurrencyUnit {
@@ -27,10 +24,25 @@ impl CurrencyUnit {
         display_name: &str,
     ) -> Self {
         Self {
-            code: code.to_string(),
+            code: Cow::Owned(code.to_string()),
             numeric_code,
             default_fraction_digits,
-            display_name: display_name.to_string(),
+            display_name: Cow::Owned(display_name.to_string()),
+        }
+    }
+
+    /// Const constructor for units backed by static data, such as the built-in currency tables
+    pub const fn from_static(
+        code: &'static str,
+        numeric_code: i32,
+        default_fraction_digits: i32,
+        display_name: &'static str,
+    ) -> Self {
+        Self {
+            code: Cow::Borrowed(code),
+            numeric_code,
+            default_fraction_digits,
+            display_name: Cow::Borrowed(display_name),
         }
     }
 
@@ -0,0 +1,331 @@
+// ISO 4217 dataset
+// ================
+//
+// Alpha code, numeric code, minor units, name and display symbol for every code in
+// ISO 4217 List One, plus currencies that have been withdrawn. Codes whose minor unit
+// is "N.A." in the standard (metals, funds, XDR...) use 4 digits, the precision this
+// crate already used for precious metals. Symbols stay distinct where the local sign
+// is shared ("£", "kr", "Rs"...) by falling back to a prefixed form or the code.
+
+use chrono::NaiveDate;
+use crate::core::currency::Currency;
+use crate::core::currency_unit::CurrencyUnit;
+
+/// (alpha code, numeric code, minor units, name, symbol)
+type Entry = (&'static str, i32, i32, &'static str, &'static str);
+
+/// (alpha code, numeric code, minor units, name, symbol, withdrawn on)
+type HistoricalEntry = (&'static str, i32, i32, &'static str, &'static str, (i32, u32, u32));
+
+const ACTIVE: &[Entry] = &[
+    ("AED", 784, 2, "UAE Dirham", "د.إ"),
+    ("AFN", 971, 2, "Afghani", "؋"),
+    ("ALL", 8, 2, "Lek", "Lek"),
+    ("AMD", 51, 2, "Armenian Dram", "֏"),
+    ("AOA", 973, 2, "Kwanza", "Kz"),
+    ("ARS", 32, 2, "Argentine Peso", "AR$"),
+    ("AUD", 36, 2, "Australian Dollar", "A$"),
+    ("AWG", 533, 2, "Aruban Florin", "ƒ"),
+    ("AZN", 944, 2, "Azerbaijan Manat", "₼"),
+    ("BAM", 977, 2, "Convertible Mark", "KM"),
+    ("BBD", 52, 2, "Barbados Dollar", "Bds$"),
+    ("BDT", 50, 2, "Taka", "৳"),
+    ("BHD", 48, 3, "Bahraini Dinar", "BD"),
+    ("BIF", 108, 0, "Burundi Franc", "FBu"),
+    ("BMD", 60, 2, "Bermudian Dollar", "BD$"),
+    ("BND", 96, 2, "Brunei Dollar", "B$"),
+    ("BOB", 68, 2, "Boliviano", "Bs"),
+    ("BOV", 984, 2, "Mvdol", "BOV"),
+    ("BRL", 986, 2, "Brazilian Real", "R$"),
+    ("BSD", 44, 2, "Bahamian Dollar", "BS$"),
+    ("BTN", 64, 2, "Ngultrum", "Nu."),
+    ("BWP", 72, 2, "Pula", "P"),
+    ("BYN", 933, 2, "Belarusian Ruble", "Br"),
+    ("BZD", 84, 2, "Belize Dollar", "BZ$"),
+    ("CAD", 124, 2, "Canadian Dollar", "C$"),
+    ("CDF", 976, 2, "Congolese Franc", "FC"),
+    ("CHE", 947, 2, "WIR Euro", "CHE"),
+    ("CHF", 756, 2, "Swiss Franc", "Fr"),
+    ("CHW", 948, 2, "WIR Franc", "CHW"),
+    ("CLF", 990, 4, "Unidad de Fomento", "UF"),
+    ("CLP", 152, 0, "Chilean Peso", "CLP$"),
+    ("CNY", 156, 2, "Chinese Yuan", "¥"),
+    ("COP", 170, 2, "Colombian Peso", "COL$"),
+    ("COU", 970, 2, "Unidad de Valor Real", "COU"),
+    ("CRC", 188, 2, "Costa Rican Colon", "₡"),
+    ("CUC", 931, 2, "Peso Convertible", "CUC$"),
+    ("CUP", 192, 2, "Cuban Peso", "CUP"),
+    ("CVE", 132, 2, "Cabo Verde Escudo", "Esc"),
+    ("CZK", 203, 2, "Czech Koruna", "Kč"),
+    ("DJF", 262, 0, "Djibouti Franc", "Fdj"),
+    ("DKK", 208, 2, "Danish Krone", "kr"),
+    ("DOP", 214, 2, "Dominican Peso", "RD$"),
+    ("DZD", 12, 2, "Algerian Dinar", "DA"),
+    ("EGP", 818, 2, "Egyptian Pound", "E£"),
+    ("ERN", 232, 2, "Nakfa", "Nfk"),
+    ("ETB", 230, 2, "Ethiopian Birr", "ETB"),
+    ("EUR", 978, 2, "Euro", "€"),
+    ("FJD", 242, 2, "Fiji Dollar", "FJ$"),
+    ("FKP", 238, 2, "Falkland Islands Pound", "FK£"),
+    ("GBP", 826, 2, "British Pound Sterling", "£"),
+    ("GEL", 981, 2, "Lari", "₾"),
+    ("GHS", 936, 2, "Ghana Cedi", "₵"),
+    ("GIP", 292, 2, "Gibraltar Pound", "GIP"),
+    ("GMD", 270, 2, "Dalasi", "D"),
+    ("GNF", 324, 0, "Guinean Franc", "FG"),
+    ("GTQ", 320, 2, "Quetzal", "Q"),
+    ("GYD", 328, 2, "Guyana Dollar", "GY$"),
+    ("HKD", 344, 2, "Hong Kong Dollar", "HK$"),
+    ("HNL", 340, 2, "Lempira", "HNL"),
+    ("HTG", 332, 2, "Gourde", "G"),
+    ("HUF", 348, 2, "Hungarian Forint", "Ft"),
+    ("IDR", 360, 2, "Indonesian Rupiah", "Rp"),
+    ("ILS", 376, 2, "Israeli New Shekel", "₪"),
+    ("INR", 356, 2, "Indian Rupee", "₹"),
+    ("IQD", 368, 3, "Iraqi Dinar", "IQD"),
+    ("IRR", 364, 2, "Iranian Rial", "IRR"),
+    ("ISK", 352, 0, "Icelandic Króna", "kr"),
+    ("JMD", 388, 2, "Jamaican Dollar", "J$"),
+    ("JOD", 400, 3, "Jordanian Dinar", "JD"),
+    ("JPY", 392, 0, "Japanese Yen", "¥"),
+    ("KES", 404, 2, "Kenyan Shilling", "KSh"),
+    ("KGS", 417, 2, "Som", "сом"),
+    ("KHR", 116, 2, "Riel", "៛"),
+    ("KMF", 174, 0, "Comorian Franc", "CF"),
+    ("KPW", 408, 2, "North Korean Won", "KPW"),
+    ("KRW", 410, 0, "South Korean Won", "₩"),
+    ("KWD", 414, 3, "Kuwaiti Dinar", "KD"),
+    ("KYD", 136, 2, "Cayman Islands Dollar", "CI$"),
+    ("KZT", 398, 2, "Tenge", "₸"),
+    ("LAK", 418, 2, "Lao Kip", "₭"),
+    ("LBP", 422, 2, "Lebanese Pound", "LBP"),
+    ("LKR", 144, 2, "Sri Lanka Rupee", "LKR"),
+    ("LRD", 430, 2, "Liberian Dollar", "L$"),
+    ("LSL", 426, 2, "Loti", "LSL"),
+    ("LYD", 434, 3, "Libyan Dinar", "LD"),
+    ("MAD", 504, 2, "Moroccan Dirham", "د.م."),
+    ("MDL", 498, 2, "Moldovan Leu", "MDL"),
+    ("MGA", 969, 2, "Malagasy Ariary", "Ar"),
+    ("MKD", 807, 2, "Denar", "ден"),
+    ("MMK", 104, 2, "Kyat", "K"),
+    ("MNT", 496, 2, "Tugrik", "₮"),
+    ("MOP", 446, 2, "Pataca", "MOP$"),
+    ("MRU", 929, 2, "Ouguiya", "UM"),
+    ("MUR", 480, 2, "Mauritius Rupee", "MUR"),
+    ("MVR", 462, 2, "Rufiyaa", "Rf"),
+    ("MWK", 454, 2, "Malawi Kwacha", "MK"),
+    ("MXN", 484, 2, "Mexican Peso", "$"),
+    ("MXV", 979, 2, "Mexican Unidad de Inversion (UDI)", "MXV"),
+    ("MYR", 458, 2, "Malaysian Ringgit", "RM"),
+    ("MZN", 943, 2, "Mozambique Metical", "MT"),
+    ("NAD", 516, 2, "Namibia Dollar", "N$"),
+    ("NGN", 566, 2, "Nigerian Naira", "₦"),
+    ("NIO", 558, 2, "Cordoba Oro", "NIO"),
+    ("NOK", 578, 2, "Norwegian Krone", "kr"),
+    ("NPR", 524, 2, "Nepalese Rupee", "NPR"),
+    ("NZD", 554, 2, "New Zealand Dollar", "NZ$"),
+    ("OMR", 512, 3, "Rial Omani", "ر.ع."),
+    ("PAB", 590, 2, "Balboa", "B/."),
+    ("PEN", 604, 2, "Sol", "S/"),
+    ("PGK", 598, 2, "Kina", "PGK"),
+    ("PHP", 608, 2, "Philippine Peso", "₱"),
+    ("PKR", 586, 2, "Pakistan Rupee", "Rs"),
+    ("PLN", 985, 2, "Polish Zloty", "zł"),
+    ("PYG", 600, 0, "Guarani", "₲"),
+    ("QAR", 634, 2, "Qatari Riyal", "QR"),
+    ("RON", 946, 2, "Romanian Leu", "lei"),
+    ("RSD", 941, 2, "Serbian Dinar", "дин."),
+    ("RUB", 643, 2, "Russian Ruble", "₽"),
+    ("RWF", 646, 0, "Rwanda Franc", "FRw"),
+    ("SAR", 682, 2, "Saudi Riyal", "﷼"),
+    ("SBD", 90, 2, "Solomon Islands Dollar", "SI$"),
+    ("SCR", 690, 2, "Seychelles Rupee", "SCR"),
+    ("SDG", 938, 2, "Sudanese Pound", "SDG"),
+    ("SEK", 752, 2, "Swedish Krona", "kr"),
+    ("SGD", 702, 2, "Singapore Dollar", "S$"),
+    ("SHP", 654, 2, "Saint Helena Pound", "SHP"),
+    ("SLE", 925, 2, "Leone", "Le"),
+    ("SOS", 706, 2, "Somali Shilling", "Sh.So."),
+    ("SRD", 968, 2, "Surinam Dollar", "SRD"),
+    ("SSP", 728, 2, "South Sudanese Pound", "SSP"),
+    ("STN", 930, 2, "Dobra", "Db"),
+    ("SVC", 222, 2, "El Salvador Colon", "SVC"),
+    ("SYP", 760, 2, "Syrian Pound", "SYP"),
+    ("SZL", 748, 2, "Lilangeni", "SZL"),
+    ("THB", 764, 2, "Thai Baht", "฿"),
+    ("TJS", 972, 2, "Somoni", "SM"),
+    ("TMT", 934, 2, "Turkmenistan New Manat", "TMT"),
+    ("TND", 788, 3, "Tunisian Dinar", "DT"),
+    ("TOP", 776, 2, "Pa'anga", "T$"),
+    ("TRY", 949, 2, "Turkish Lira", "₺"),
+    ("TTD", 780, 2, "Trinidad and Tobago Dollar", "TT$"),
+    ("TWD", 901, 2, "New Taiwan Dollar", "NT$"),
+    ("TZS", 834, 2, "Tanzanian Shilling", "TSh"),
+    ("UAH", 980, 2, "Hryvnia", "₴"),
+    ("UGX", 800, 0, "Uganda Shilling", "USh"),
+    ("USD", 840, 2, "US Dollar", "$"),
+    ("USN", 997, 2, "US Dollar (Next day)", "USN"),
+    ("UYI", 940, 0, "Uruguay Peso en Unidades Indexadas (UI)", "UYI"),
+    ("UYU", 858, 2, "Peso Uruguayo", "$U"),
+    ("UYW", 927, 4, "Unidad Previsional", "UYW"),
+    ("UZS", 860, 2, "Uzbekistan Sum", "UZS"),
+    ("VED", 926, 2, "Bolívar Soberano", "Bs.D"),
+    ("VES", 928, 2, "Bolívar Soberano", "Bs.S"),
+    ("VND", 704, 0, "Dong", "₫"),
+    ("VUV", 548, 0, "Vatu", "VT"),
+    ("WST", 882, 2, "Tala", "WS$"),
+    ("XAF", 950, 0, "CFA Franc BEAC", "FCFA"),
+    ("XAG", 961, 4, "Silver (troy ounce)", "Ag"),
+    ("XAU", 959, 4, "Gold (troy ounce)", "Au"),
+    ("XBA", 955, 4, "Bond Markets Unit European Composite Unit (EURCO)", "XBA"),
+    ("XBB", 956, 4, "Bond Markets Unit European Monetary Unit (E.M.U.-6)", "XBB"),
+    ("XBC", 957, 4, "Bond Markets Unit European Unit of Account 9 (E.U.A.-9)", "XBC"),
+    ("XBD", 958, 4, "Bond Markets Unit European Unit of Account 17 (E.U.A.-17)", "XBD"),
+    ("XCD", 951, 2, "East Caribbean Dollar", "EC$"),
+    ("XCG", 532, 2, "Caribbean Guilder", "Cg"),
+    ("XDR", 960, 4, "SDR (Special Drawing Right)", "XDR"),
+    ("XOF", 952, 0, "CFA Franc BCEAO", "CFA"),
+    ("XPD", 964, 4, "Palladium (troy ounce)", "Pd"),
+    ("XPF", 953, 0, "CFP Franc", "CFP"),
+    ("XPT", 962, 4, "Platinum (troy ounce)", "Pt"),
+    ("XSU", 994, 4, "Sucre", "XSU"),
+    ("XTS", 963, 4, "Code reserved for testing", "XTS"),
+    ("XUA", 965, 4, "ADB Unit of Account", "XUA"),
+    ("XXX", 999, 4, "No currency", "XXX"),
+    ("YER", 886, 2, "Yemeni Rial", "YER"),
+    ("ZAR", 710, 2, "South African Rand", "R"),
+    ("ZMW", 967, 2, "Zambian Kwacha", "ZK"),
+    ("ZWG", 924, 2, "Zimbabwe Gold", "ZiG"),
+];
+
+// Withdrawal dates are the day the successor currency became the official one
+const HISTORICAL: &[HistoricalEntry] = &[
+    ("ANG", 532, 2, "Netherlands Antillean Guilder", "ANG", (2025, 3, 31)),
+    ("ATS", 40, 2, "Schilling", "ATS", (2002, 1, 1)),
+    ("BEF", 56, 0, "Belgian Franc", "BEF", (2002, 1, 1)),
+    ("BGN", 975, 2, "Bulgarian Lev", "лв", (2026, 1, 1)),
+    ("BYR", 974, 0, "Belarusian Ruble", "BYR", (2016, 7, 1)),
+    ("CYP", 196, 2, "Cyprus Pound", "CYP", (2008, 1, 1)),
+    ("DEM", 276, 2, "Deutsche Mark", "DM", (2002, 1, 1)),
+    ("EEK", 233, 2, "Kroon", "EEK", (2011, 1, 1)),
+    ("ESP", 724, 0, "Spanish Peseta", "₧", (2002, 1, 1)),
+    ("FIM", 246, 2, "Markka", "FIM", (2002, 1, 1)),
+    ("FRF", 250, 2, "French Franc", "₣", (2002, 1, 1)),
+    ("GHC", 288, 2, "Cedi", "GHC", (2007, 7, 1)),
+    ("GRD", 300, 0, "Drachma", "₯", (2002, 1, 1)),
+    ("HRK", 191, 2, "Croatian Kuna", "kn", (2023, 1, 1)),
+    ("IEP", 372, 2, "Irish Pound", "IEP", (2002, 1, 1)),
+    ("ITL", 380, 0, "Italian Lira", "₤", (2002, 1, 1)),
+    ("LTL", 440, 2, "Lithuanian Litas", "LTL", (2015, 1, 1)),
+    ("LUF", 442, 0, "Luxembourg Franc", "LUF", (2002, 1, 1)),
+    ("LVL", 428, 2, "Latvian Lats", "LVL", (2014, 1, 1)),
+    ("MRO", 478, 2, "Ouguiya", "MRO", (2018, 1, 1)),
+    ("MTL", 470, 2, "Maltese Lira", "MTL", (2008, 1, 1)),
+    ("MZM", 508, 2, "Mozambique Metical", "MZM", (2006, 7, 1)),
+    ("NLG", 528, 2, "Netherlands Guilder", "NLG", (2002, 1, 1)),
+    ("PTE", 620, 0, "Portuguese Escudo", "PTE", (2002, 1, 1)),
+    ("ROL", 642, 2, "Romanian Leu", "ROL", (2005, 7, 1)),
+    ("RUR", 810, 2, "Russian Ruble", "RUR", (1998, 1, 1)),
+    ("SIT", 705, 2, "Tolar", "SIT", (2007, 1, 1)),
+    ("SKK", 703, 2, "Slovak Koruna", "SKK", (2009, 1, 1)),
+    ("SLL", 694, 2, "Leone", "SLL", (2022, 7, 1)),
+    ("STD", 678, 2, "Dobra", "STD", (2018, 1, 1)),
+    ("TRL", 792, 0, "Old Turkish Lira", "TRL", (2005, 1, 1)),
+    ("VEB", 862, 2, "Bolívar", "VEB", (2008, 1, 1)),
+    ("VEF", 937, 2, "Bolívar Fuerte", "VEF", (2018, 8, 20)),
+    ("XEU", 954, 4, "European Currency Unit", "XEU", (1999, 1, 1)),
+    ("ZMK", 894, 2, "Zambian Kwacha", "ZMK", (2013, 1, 1)),
+    ("ZWL", 932, 2, "Zimbabwe Dollar", "ZWL", (2024, 4, 8)),
+];
+
+/// Every code currently in ISO 4217 List One
+pub fn active_currencies() -> Vec<Currency> {
+    ACTIVE.iter().map(active_currency).collect()
+}
+
+/// Withdrawn ISO 4217 currencies, each carrying its withdrawal date
+pub fn historical_currencies() -> Vec<Currency> {
+    HISTORICAL.iter().map(historical_currency).collect()
+}
+
+/// Look up `code` in the dataset at compile time; used for the named currency constants.
+/// Panics (a compile error in const context) if the code is not in the dataset.
+pub(crate) const fn currency(code: &str) -> Currency {
+    let mut i = 0;
+    while i < ACTIVE.len() {
+        if str_eq(ACTIVE[i].0, code) {
+            return active_currency(&ACTIVE[i]);
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < HISTORICAL.len() {
+        if str_eq(HISTORICAL[i].0, code) {
+            return historical_currency(&HISTORICAL[i]);
+        }
+        i += 1;
+    }
+    panic!("currency code is not in the ISO 4217 dataset")
+}
+
+const fn active_currency(&(code, numeric, minor, name, symbol): &Entry) -> Currency {
+    Currency::from_static(CurrencyUnit::from_static(code, numeric, minor, name), symbol)
+}
+
+const fn historical_currency(&(code, numeric, minor, name, symbol, (year, month, day)): &HistoricalEntry) -> Currency {
+    let withdrawn_on = NaiveDate::from_ymd_opt(year, month, day).expect("valid withdrawal date");
+    active_currency(&(code, numeric, minor, name, symbol)).withdrawn(withdrawn_on)
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_codes_are_unique() {
+        let mut codes = HashSet::new();
+        for currency in active_currencies().iter().chain(historical_currencies().iter()) {
+            assert_eq!(currency.code().len(), 3, "{}", currency.code());
+            assert!(codes.insert(currency.code().to_string()), "duplicate {}", currency.code());
+        }
+
+        // Numeric codes are only reused across the active/withdrawn boundary (ANG -> XCG)
+        let mut numeric = HashSet::new();
+        for currency in active_currencies() {
+            assert!(numeric.insert(currency.numeric_code()), "duplicate {}", currency.numeric_code());
+        }
+    }
+
+    #[test]
+    fn test_minor_units() {
+        let minor = |code: &str| {
+            active_currencies()
+                .into_iter()
+                .find(|c| c.code() == code)
+                .map(|c| c.precision())
+        };
+        assert_eq!(minor("BHD"), Some(3));
+        assert_eq!(minor("KWD"), Some(3));
+        assert_eq!(minor("OMR"), Some(3));
+        assert_eq!(minor("CLF"), Some(4));
+        assert_eq!(minor("UYW"), Some(4));
+        assert_eq!(minor("JPY"), Some(0));
+        assert_eq!(minor("HRK"), None);
+    }
+}
@@ -0,0 +1,462 @@
+// Integer minor-unit amounts
+// ==========================
+//
+// `MinorUnits` stores an amount as an integer count of minor units plus a decimal
+// scale, the way ledgers usually persist money (e.g. 1234 with scale 2 for 12.34).
+// Arithmetic is done in i128 and narrowed back to the backing integer, so overflow
+// is detected instead of wrapping.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Sub};
+use rust_decimal::Decimal;
+use crate::core::{Monetary, Monetizable, MoneyError};
+use crate::core::currency::Currency;
+use crate::core::rounding::{round_quotient, round_units};
+use crate::constants::RoundingMode;
+
+/// Integer types that can back a `MinorUnits` amount
+pub trait UnitRepr: Copy + Default + Ord + fmt::Debug + Send + Sync + 'static {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Option<Self>;
+}
+
+impl UnitRepr for i64 {
+    #[inline]
+    fn to_i128(self) -> i128 {
+        self as i128
+    }
+
+    #[inline]
+    fn from_i128(value: i128) -> Option<Self> {
+        i64::try_from(value).ok()
+    }
+}
+
+impl UnitRepr for i128 {
+    #[inline]
+    fn to_i128(self) -> i128 {
+        self
+    }
+
+    #[inline]
+    fn from_i128(value: i128) -> Option<Self> {
+        Some(value)
+    }
+}
+
+/// Largest scale accepted, matching `Decimal`
+pub const MAX_SCALE: u32 = 28;
+
+/// Decimal places division keeps when both operands have fewer: twice the default
+/// `MonetaryContext` rate scale, so an inverse or cross rate stays precise enough to
+/// apply to large amounts
+pub const DIVISION_SCALE: u32 = 12;
+
+/// Amount held as an integer number of minor units with a decimal scale.
+///
+/// Addition, subtraction and multiplication are exact; a product whose scale would
+/// exceed 28 is rounded half-even to 28. Division rounds half-even to `DIVISION_SCALE`
+/// places, or the larger operand scale if that is more, and only falls back to the
+/// operand scale when the backing integer cannot hold the extra digits. The operators
+/// panic on overflow like the built-in integers; use the `checked_*` methods to get a
+/// `MoneyError` instead.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinorUnits<R: UnitRepr = i64> {
+    units: R,
+    scale: u32,
+}
+
+/// `MinorUnits` backed by i128 for amounts beyond ±9.2e18 minor units
+pub type MinorUnits128 = MinorUnits<i128>;
+
+impl<R: UnitRepr> MinorUnits<R> {
+    /// `units` minor units at `scale` decimal places; returns `ScaleOutOfRange` for
+    /// scales above 28
+    pub fn new(units: R, scale: u32) -> Result<Self, MoneyError> {
+        Ok(Self {
+            units,
+            scale: check_scale(scale)?,
+        })
+    }
+
+    /// `units` in the minor unit of `currency`, e.g. cents for USD
+    pub fn for_currency(units: R, currency: &Currency) -> Result<Self, MoneyError> {
+        Self::new(units, currency_scale(currency)?)
+    }
+
+    /// Convert a decimal to the minor unit of `currency`, failing if digits would be lost
+    pub fn from_decimal_for(value: Decimal, currency: &Currency) -> Result<Self, MoneyError> {
+        Self::try_from_decimal(value)?.rescale(currency_scale(currency)?)
+    }
+
+    pub fn units(&self) -> R {
+        self.units
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Change the scale without losing digits; returns `PrecisionLoss` if the
+    /// amount has non-zero digits beyond the new scale, or `ScaleOutOfRange` for
+    /// scales above 28
+    pub fn rescale(&self, scale: u32) -> Result<Self, MoneyError> {
+        let scale = check_scale(scale)?;
+        let units = if scale >= self.scale {
+            self.units
+                .to_i128()
+                .checked_mul(pow10(scale - self.scale)?)
+                .ok_or_else(|| overflow("rescale"))?
+        } else {
+            let divisor = pow10(self.scale - scale)?;
+            let units = self.units.to_i128();
+            if units % divisor != 0 {
+                return Err(MoneyError::PrecisionLoss);
+            }
+            units / divisor
+        };
+        Self::from_parts(units, scale, "rescale")
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, MoneyError> {
+        let (a, b, scale) = align(self, rhs, "addition")?;
+        Self::from_parts(a.checked_add(b).ok_or_else(|| overflow("addition"))?, scale, "addition")
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, MoneyError> {
+        let (a, b, scale) = align(self, rhs, "subtraction")?;
+        Self::from_parts(a.checked_sub(b).ok_or_else(|| overflow("subtraction"))?, scale, "subtraction")
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, MoneyError> {
+        let product = self
+            .units
+            .to_i128()
+            .checked_mul(rhs.units.to_i128())
+            .ok_or_else(|| overflow("multiplication"))?;
+        // The exact product has scale self.scale + rhs.scale
+        let scale = self.scale + rhs.scale;
+        if scale <= MAX_SCALE {
+            return Self::from_parts(product, scale, "multiplication");
+        }
+        let units = round_quotient(product, pow10(scale - MAX_SCALE)?, &RoundingMode::HalfEven)?;
+        Self::from_parts(units, MAX_SCALE, "multiplication")
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self, MoneyError> {
+        let divisor = rhs.units.to_i128();
+        if divisor == 0 {
+            return Err(MoneyError::DivisionByZero);
+        }
+        // units / divisor * 10^(scale - self.scale + rhs.scale) keeps `scale` digits
+        let quotient = |scale: u32| {
+            let dividend = self
+                .units
+                .to_i128()
+                .checked_mul(pow10(scale - self.scale + rhs.scale)?)
+                .ok_or_else(|| overflow("division"))?;
+            Self::from_parts(round_quotient(dividend, divisor, &RoundingMode::HalfEven)?, scale, "division")
+        };
+        let scale = self.scale.max(rhs.scale);
+        if scale < DIVISION_SCALE {
+            if let Ok(result) = quotient(DIVISION_SCALE) {
+                return Ok(result);
+            }
+        }
+        quotient(scale)
+    }
+
+    fn from_parts(units: i128, scale: u32, operation: &str) -> Result<Self, MoneyError> {
+        R::from_i128(units)
+            .map(|units| Self { units, scale })
+            .ok_or_else(|| overflow(operation))
+    }
+}
+
+fn check_scale(scale: u32) -> Result<u32, MoneyError> {
+    if scale > MAX_SCALE {
+        return Err(MoneyError::ScaleOutOfRange(scale as i64));
+    }
+    Ok(scale)
+}
+
+fn currency_scale(currency: &Currency) -> Result<u32, MoneyError> {
+    check_scale(currency.precision().max(0) as u32)
+}
+
+fn overflow(operation: &str) -> MoneyError {
+    MoneyError::Overflow(format!("MinorUnits {}", operation))
+}
+
+fn pow10(exponent: u32) -> Result<i128, MoneyError> {
+    10i128.checked_pow(exponent).ok_or(MoneyError::ScaleOutOfRange(exponent as i64))
+}
+
+/// Bring both operands to the larger scale
+fn align<R: UnitRepr>(a: MinorUnits<R>, b: MinorUnits<R>, operation: &str) -> Result<(i128, i128, u32), MoneyError> {
+    let scale = a.scale.max(b.scale);
+    let scale_up = |value: MinorUnits<R>| {
+        value
+            .units
+            .to_i128()
+            .checked_mul(pow10(scale - value.scale)?)
+            .ok_or_else(|| overflow(operation))
+    };
+    Ok((scale_up(a)?, scale_up(b)?, scale))
+}
+
+impl<R: UnitRepr> PartialEq for MinorUnits<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+// Numeric comparison: 1.5 (15, scale 1) equals 1.50 (150, scale 2)
+impl<R: UnitRepr> PartialOrd for MinorUnits<R> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match align(*self, *other, "comparison") {
+            Ok((a, b, _)) => Some(a.cmp(&b)),
+            // Only the operand being scaled up can overflow, so its magnitude dominates
+            Err(_) if self.scale < other.scale => Some(self.units.cmp(&R::default())),
+            Err(_) => Some(R::default().cmp(&other.units)),
+        }
+    }
+}
+
+impl<R: UnitRepr> fmt::Display for MinorUnits<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let units = self.units.to_i128();
+        let digits = units.unsigned_abs().to_string();
+        let sign = if units < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+        let padded = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = padded.split_at(padded.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl<R: UnitRepr> Add for MinorUnits<R> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs).expect("MinorUnits addition overflowed")
+    }
+}
+
+impl<R: UnitRepr> Sub for MinorUnits<R> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("MinorUnits subtraction overflowed")
+    }
+}
+
+impl<R: UnitRepr> Mul for MinorUnits<R> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs).expect("MinorUnits multiplication overflowed")
+    }
+}
+
+impl<R: UnitRepr> Div for MinorUnits<R> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("MinorUnits division failed")
+    }
+}
+
+impl<R: UnitRepr> Monetizable for MinorUnits<R> {
+    #[inline]
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.units == R::default()
+    }
+
+    fn try_from_f64(val: f64) -> Result<Self, MoneyError> {
+        Self::try_from_decimal(Decimal::try_from_f64(val)?)
+    }
+
+    fn try_to_f64(&self) -> Result<f64, MoneyError> {
+        self.try_to_decimal()?.try_to_f64()
+    }
+
+    fn try_from_f32(val: f32) -> Result<Self, MoneyError> {
+        Self::try_from_decimal(Decimal::try_from_f32(val)?)
+    }
+
+    fn try_to_f32(&self) -> Result<f32, MoneyError> {
+        self.try_to_decimal()?.try_to_f32()
+    }
+
+    /// Keeps the decimal's own scale, minus trailing zeros if the integer would not fit
+    fn try_from_decimal(val: Decimal) -> Result<Self, MoneyError> {
+        if let Some(units) = R::from_i128(val.mantissa()) {
+            return Ok(Self { units, scale: val.scale() });
+        }
+        let normalized = val.normalize();
+        Self::from_parts(normalized.mantissa(), normalized.scale(), "conversion from Decimal")
+    }
+
+    fn try_to_decimal(&self) -> Result<Decimal, MoneyError> {
+        Decimal::try_from_i128_with_scale(self.units.to_i128(), self.scale)
+            .map_err(|_| MoneyError::Overflow(format!("{} does not fit in a Decimal", self)))
+    }
+
+    fn checked_add(self, rhs: Self) -> Result<Self, MoneyError> {
+        MinorUnits::checked_add(self, rhs)
+    }
+
+    fn checked_sub(self, rhs: Self) -> Result<Self, MoneyError> {
+        MinorUnits::checked_sub(self, rhs)
+    }
+
+    fn checked_mul(self, rhs: Self) -> Result<Self, MoneyError> {
+        MinorUnits::checked_mul(self, rhs)
+    }
+
+    fn checked_div(self, rhs: Self) -> Result<Self, MoneyError> {
+        MinorUnits::checked_div(self, rhs)
+    }
+
+    fn round_to_scale(self, scale: i32, rounding_mode: &RoundingMode) -> Result<Self, MoneyError> {
+        let (units, scale) = round_units(self.units.to_i128(), self.scale as i32, scale, rounding_mode)?;
+        Self::from_parts(units, scale as u32, "rounding")
+    }
+}
+
+impl<R: UnitRepr> Monetary<MinorUnits<R>> {
+    /// Amount of `units` in the minor unit of `currency`
+    pub fn from_minor_units(units: R, currency: Currency) -> Result<Self, MoneyError> {
+        Ok(Self::new(MinorUnits::for_currency(units, &currency)?, currency))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use crate::core::currency_unit::CurrencyUnit;
+    use crate::exchange::base_exchange::{CurrencyConversion, ExchangeRateProvider};
+    use crate::exchange::static_exchange::StaticRateProvider;
+
+    #[test]
+    fn test_display_and_decimal_round_trip() {
+        assert_eq!(MinorUnits::<i64>::new(1234, 2).unwrap().to_string(), "12.34");
+        assert_eq!(MinorUnits::<i64>::new(-5, 3).unwrap().to_string(), "-0.005");
+        assert_eq!(MinorUnits::<i64>::new(700, 0).unwrap().to_string(), "700");
+
+        for text in ["12.34", "-0.005", "700", "0.000000000000000001"] {
+            let decimal = Decimal::from_str(text).unwrap();
+            let units = MinorUnits::<i64>::try_from_decimal(decimal).unwrap();
+            assert_eq!(units.try_to_decimal().unwrap(), decimal);
+            assert_eq!(units.try_to_decimal().unwrap().scale(), decimal.scale());
+        }
+
+        let price = MinorUnits::<i64>::from_decimal_for(Decimal::from_str("12.3").unwrap(), &Currency::usd()).unwrap();
+        assert_eq!((price.units(), price.scale()), (1230, 2));
+        assert_eq!(
+            MinorUnits::<i64>::from_decimal_for(Decimal::from_str("12.345").unwrap(), &Currency::usd()),
+            Err(MoneyError::PrecisionLoss)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = MinorUnits::<i64>::new(1050, 2).unwrap();
+        let b = MinorUnits::<i64>::new(25, 1).unwrap();
+        assert_eq!(a + b, MinorUnits::new(1300, 2).unwrap());
+        assert_eq!(a - b, MinorUnits::new(800, 2).unwrap());
+        assert_eq!(a * MinorUnits::new(3, 0).unwrap(), MinorUnits::new(3150, 2).unwrap());
+        // 10.50 * 0.85 = 8.9250, kept exact
+        let product = a * MinorUnits::new(85, 2).unwrap();
+        assert_eq!((product.units(), product.scale()), (89250, 4));
+        assert_eq!(
+            MinorUnits::<i64>::new(15, 20).unwrap().checked_mul(MinorUnits::new(25, 10).unwrap()).unwrap(),
+            MinorUnits::new(4, 28).unwrap()
+        );
+        // Division keeps DIVISION_SCALE places, unless the backing integer cannot hold them
+        let third = MinorUnits::<i64>::new(1000, 2).unwrap() / MinorUnits::new(3, 0).unwrap();
+        assert_eq!((third.units(), third.scale()), (3_333_333_333_333, 12));
+        let inverse = MinorUnits::<i64>::new(1, 0).unwrap() / MinorUnits::new(15012, 2).unwrap();
+        assert_eq!(inverse.to_string(), "0.006661337597");
+        let large = MinorUnits::<i64>::new(i64::MAX, 2).unwrap() / MinorUnits::new(1, 0).unwrap();
+        assert_eq!((large.units(), large.scale()), (i64::MAX, 2));
+        assert_eq!(MinorUnits::<i64>::new(15, 1).unwrap(), MinorUnits::new(150, 2).unwrap());
+        assert!(MinorUnits::<i64>::new(-1, 0).unwrap() < MinorUnits::new(1, 28).unwrap());
+    }
+
+    #[test]
+    fn test_overflow_is_reported() {
+        let max = MinorUnits::<i64>::new(i64::MAX, 2).unwrap();
+        assert!(matches!(max.checked_add(MinorUnits::new(1, 2).unwrap()), Err(MoneyError::Overflow(_))));
+        assert!(matches!(max.checked_mul(MinorUnits::new(2, 0).unwrap()), Err(MoneyError::Overflow(_))));
+        assert_eq!(max.checked_div(MinorUnits::zero()), Err(MoneyError::DivisionByZero));
+
+        let wide = MinorUnits128::new(i64::MAX as i128, 2).unwrap();
+        assert_eq!(wide.checked_add(MinorUnits::new(1, 2).unwrap()).unwrap().units(), i64::MAX as i128 + 1);
+    }
+
+    #[test]
+    fn test_scale_out_of_range() {
+        assert_eq!(MinorUnits::<i64>::new(1, 30), Err(MoneyError::ScaleOutOfRange(30)));
+        assert_eq!(MinorUnits::<i64>::new(1, 2).unwrap().rescale(29), Err(MoneyError::ScaleOutOfRange(29)));
+
+        let fine = Currency::new(CurrencyUnit::new("XFN", 0, 30, "Fine Units"), "xfn");
+        assert_eq!(MinorUnits::<i64>::for_currency(1, &fine), Err(MoneyError::ScaleOutOfRange(30)));
+        assert_eq!(Monetary::from_minor_units(1i64, fine).map(|_| ()), Err(MoneyError::ScaleOutOfRange(30)));
+    }
+
+    #[test]
+    fn test_works_with_monetary_and_conversion() {
+        let usd = Currency::usd();
+        let jpy = Currency::jpy();
+        let price = Monetary::from_minor_units(1999i64, usd.clone()).unwrap();
+        let total = (price.clone() + price.clone()).unwrap();
+        assert_eq!(total.amount, MinorUnits::new(3998, 2).unwrap());
+        assert_eq!(total.to_string(), "39.98 USD");
+
+        let mut provider = StaticRateProvider::new();
+        provider.add_rate(&usd, &jpy, MinorUnits::new(15012, 2).unwrap());
+        let rate = provider.get_exchange_rate(&usd, &jpy).unwrap();
+        // 19.99 * 150.12 = 3000.8988, within the context's max_scale
+        let yen = rate.apply(&price).unwrap().amount;
+        assert_eq!((yen.units(), yen.scale()), (30008988, 4));
+        let yen = rate.with_currency_rounding().apply(&price).unwrap().amount;
+        assert_eq!((yen.units(), yen.scale()), (3001, 0));
+
+        let mut conversion = CurrencyConversion::new();
+        conversion.add_provider(Arc::new(provider));
+        let converted = conversion.convert(&price, &jpy).unwrap();
+        assert_eq!(converted.amount.to_string(), "3000.8988");
+        assert_eq!(conversion.convert_to::<MinorUnits>(&price, &jpy).unwrap().amount.to_string(), "3000.8988");
+
+        let as_decimal = conversion.convert_to::<Decimal>(&price, &jpy).unwrap();
+        assert_eq!(as_decimal.amount, Decimal::from_str("3000.8988").unwrap());
+    }
+
+    #[test]
+    fn test_inverse_rate_keeps_precision() {
+        let (usd, jpy) = (Currency::usd(), Currency::jpy());
+        let mut provider = StaticRateProvider::new();
+        provider.add_rate(&usd, &jpy, MinorUnits::new(15012, 2).unwrap());
+        let mut conversion = CurrencyConversion::new().with_inverse_rates();
+        conversion.add_provider(Arc::new(provider));
+
+        // 100000 / 150.12 = 666.1337..., rounded to the default max_scale of 6
+        let yen = Monetary::from_minor_units(100_000i64, jpy.clone()).unwrap();
+        let dollars = conversion.convert(&yen, &usd).unwrap().amount;
+        assert_eq!((dollars.units(), dollars.scale()), (666_133_760, 6));
+        assert_eq!(conversion.convert_to::<Decimal>(&yen, &usd).unwrap().amount.to_string(), "666.133760");
+    }
+}
@@ -0,0 +1,173 @@
+// Money bag
+// =========
+//
+// Balances held in several currencies at once, one `Monetary<T>` per currency, kept in
+// a `MultiCurrencyTotal`. Adding or subtracting an amount only touches the balance of
+// its own currency, so mixed currencies never raise `CurrencyMismatch`. Balances that
+// come back to zero stay in the bag.
+
+use std::fmt;
+use crate::core::aggregation::MultiCurrencyTotal;
+use crate::core::currency::Currency;
+use crate::core::{Monetary, Monetizable, MoneyError};
+use crate::errors::ExchangeError;
+use crate::exchange::base_exchange::CurrencyConversion;
+
+/// Balances in several currencies, kept as a `MultiCurrencyTotal` and iterated in the
+/// same order
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoneyBag<T: Monetizable + 'static> {
+    balances: MultiCurrencyTotal<T>,
+}
+
+impl<T: Monetizable + 'static> MoneyBag<T> {
+    pub fn new() -> Self {
+        Self { balances: MultiCurrencyTotal::new() }
+    }
+
+    /// Add `amount` to the balance of its currency
+    pub fn add(&mut self, amount: &Monetary<T>) -> Result<(), MoneyError> {
+        self.balances.add(amount)
+    }
+
+    /// Take `amount` from the balance of its currency; balances may go negative
+    pub fn subtract(&mut self, amount: &Monetary<T>) -> Result<(), MoneyError> {
+        self.balances.subtract(amount)
+    }
+
+    /// Balance in `currency`, if the bag has ever held it
+    pub fn get(&self, currency: &Currency) -> Option<&Monetary<T>> {
+        self.balances.get(currency)
+    }
+
+    pub fn currencies(&self) -> impl Iterator<Item = &Currency> {
+        self.balances.currencies()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Monetary<T>> {
+        self.balances.iter()
+    }
+
+    /// Number of currencies held, including zero balances
+    pub fn len(&self) -> usize {
+        self.balances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.balances.is_empty()
+    }
+
+    /// Check whether every balance is zero; true for an empty bag
+    pub fn is_zero(&self) -> bool {
+        self.balances.iter().all(Monetary::is_zero)
+    }
+}
+
+impl<T: Monetizable + Send + Sync + 'static> MoneyBag<T> {
+    /// Value of the whole bag in `target`, see `MultiCurrencyTotal::collapse_all`
+    pub fn total_in(
+        &self,
+        target: &Currency,
+        conversion: &CurrencyConversion<T>
+    ) -> Result<Monetary<T>, Vec<ExchangeError>> {
+        self.balances.collapse_all(target, conversion)
+    }
+}
+
+impl<T: Monetizable + 'static> Default for MoneyBag<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Monetizable + 'static> IntoIterator for &'a MoneyBag<T> {
+    type Item = &'a Monetary<T>;
+    type IntoIter = <&'a MultiCurrencyTotal<T> as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        (&self.balances).into_iter()
+    }
+}
+
+/// Balances joined with ", " in iteration order, e.g. "5.00 EUR, 12.50 USD"; "empty" for an empty bag
+impl<T: Monetizable + 'static> fmt::Display for MoneyBag<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "empty");
+        }
+        for (i, balance) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", balance)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use crate::exchange::static_exchange::StaticRateProvider;
+
+    fn money(value: &str, currency: Currency) -> Monetary<Decimal> {
+        Monetary::new(Decimal::from_str(value).unwrap(), currency)
+    }
+
+    fn wallet() -> MoneyBag<Decimal> {
+        let mut bag = MoneyBag::new();
+        bag.add(&money("100.00", Currency::usd())).unwrap();
+        bag.add(&money("50.00", Currency::eur())).unwrap();
+        bag.add(&money("0.015", Currency::btc())).unwrap();
+        bag.subtract(&money("20.50", Currency::usd())).unwrap();
+        bag
+    }
+
+    #[test]
+    fn test_mixed_currencies() {
+        let mut bag = wallet();
+        assert_eq!(bag.len(), 3);
+        assert_eq!(bag.get(&Currency::usd()), Some(&money("79.50", Currency::usd())));
+        assert_eq!(bag.get(&Currency::gbp()), None);
+        let codes: Vec<&str> = bag.currencies().map(Currency::code).collect();
+        assert_eq!(codes, ["BTC", "EUR", "USD"]);
+        assert_eq!(bag.to_string(), "0.015 BTC, 50.00 EUR, 79.50 USD");
+        assert!(!bag.is_zero());
+
+        // Subtracting a currency the bag does not hold leaves a negative balance
+        bag.subtract(&money("5", Currency::gbp())).unwrap();
+        assert_eq!(bag.get(&Currency::gbp()), Some(&money("-5", Currency::gbp())));
+
+        let mut empty = MoneyBag::<Decimal>::new();
+        assert!(empty.is_zero());
+        assert_eq!(empty.to_string(), "empty");
+        empty.add(&money("3", Currency::chf())).unwrap();
+        empty.subtract(&money("3", Currency::chf())).unwrap();
+        assert!(empty.is_zero());
+        assert_eq!(empty.len(), 1);
+        assert_eq!((&empty).into_iter().count(), 1);
+    }
+
+    #[test]
+    fn test_total_in() {
+        let bag = wallet();
+        let mut rates = StaticRateProvider::new();
+        rates.add_rate(&Currency::eur(), &Currency::usd(), Decimal::from_str("1.10").unwrap());
+        let mut conversion = CurrencyConversion::new();
+        conversion.add_provider(Arc::new(rates));
+
+        // No BTC/USD rate: the failure names the pair
+        let failures = bag.total_in(&Currency::usd(), &conversion).unwrap_err();
+        assert_eq!(failures, vec![ExchangeError::no_rate_found("BTC", "USD")]);
+
+        let mut rates = StaticRateProvider::new();
+        rates.add_rate(&Currency::btc(), &Currency::usd(), Decimal::from_str("60000").unwrap());
+        conversion.add_provider(Arc::new(rates));
+        let total = bag.total_in(&Currency::usd(), &conversion).unwrap();
+        assert_eq!(total.amount, Decimal::from_str("1034.50").unwrap());
+        assert_eq!(total.currency, Currency::usd());
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+use crate::core::currency::{builtin_currencies, Currency};
+use crate::errors::CurrencyError;
+
+/// Mutable set of currencies keyed by uppercase code.
+///
+/// `CurrencyRegistry::global()` backs `Currency::from_code` and friends. Services that
+/// need their own currencies without touching the process-wide set can build a scoped
+/// registry (usually `CurrencyRegistry::global().clone()` plus their additions) and pass
+/// it to `MoneyParser::with_registry` or `CurrencyConversion::with_registry`.
+#[derive(Debug, Default)]
+pub struct CurrencyRegistry {
+    // BTreeMap keeps lookups by numeric code and symbol deterministic
+    currencies: RwLock<BTreeMap<String, Currency>>,
+}
+
+static GLOBAL_REGISTRY: OnceLock<CurrencyRegistry> = OnceLock::new();
+
+impl CurrencyRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry pre-populated with the built-in currencies
+    pub fn with_defaults() -> Self {
+        let registry = Self::new();
+        for currency in builtin_currencies() {
+            registry
+                .register_or_replace(currency)
+                .expect("built-in currency codes are alphanumeric");
+        }
+        registry
+    }
+
+    /// Process-wide registry, initialized with the built-in currencies
+    pub fn global() -> &'static CurrencyRegistry {
+        GLOBAL_REGISTRY.get_or_init(Self::with_defaults)
+    }
+
+    /// Add a currency, failing if its code is already taken
+    pub fn register(&self, currency: Currency) -> Result<(), CurrencyError> {
+        let key = Self::key(currency.code())?;
+        let mut currencies = self.currencies.write().unwrap();
+        if currencies.contains_key(&key) {
+            return Err(CurrencyError::duplicate_currency(key));
+        }
+        currencies.insert(key, currency);
+        Ok(())
+    }
+
+    /// Add or replace a currency, returning the previous definition
+    pub fn register_or_replace(&self, currency: Currency) -> Result<Option<Currency>, CurrencyError> {
+        let key = Self::key(currency.code())?;
+        Ok(self.currencies.write().unwrap().insert(key, currency))
+    }
+
+    /// Remove a currency by code, returning it if it was registered
+    pub fn unregister(&self, code: &str) -> Option<Currency> {
+        self.currencies.write().unwrap().remove(&code.to_uppercase())
+    }
+
+    /// Look up a currency by alpha code (case-insensitive)
+    pub fn get(&self, code: &str) -> Option<Currency> {
+        self.currencies.read().unwrap().get(&code.to_uppercase()).cloned()
+    }
+
+    /// Look up a currency by numeric code. When several currencies share the
+    /// code, active ones win over withdrawn ones, then the lowest alpha code.
+    /// Numeric code 0 marks currencies without an ISO code and is never matched.
+    pub fn get_by_numeric(&self, numeric_code: i32) -> Option<Currency> {
+        if numeric_code == 0 {
+            return None;
+        }
+        let currencies = self.currencies.read().unwrap();
+        let mut matches = currencies
+            .values()
+            .filter(|currency| currency.numeric_code() == numeric_code);
+        let first = matches.next()?;
+        if first.is_active() {
+            return Some(first.clone());
+        }
+        Some(matches.find(|currency| currency.is_active()).unwrap_or(first).clone())
+    }
+
+    /// All currencies using `symbol`, ordered by code
+    pub fn find_by_symbol(&self, symbol: &str) -> Vec<Currency> {
+        self.currencies
+            .read()
+            .unwrap()
+            .values()
+            .filter(|currency| currency.symbol() == symbol)
+            .cloned()
+            .collect()
+    }
+
+    pub fn contains(&self, code: &str) -> bool {
+        self.currencies.read().unwrap().contains_key(&code.to_uppercase())
+    }
+
+    /// Snapshot of every registered currency, ordered by code
+    pub fn currencies(&self) -> Vec<Currency> {
+        self.currencies.read().unwrap().values().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.currencies.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.currencies.read().unwrap().is_empty()
+    }
+
+    fn key(code: &str) -> Result<String, CurrencyError> {
+        if code.is_empty() || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(CurrencyError::invalid_format_with_input("Currency codes must be alphanumeric", code));
+        }
+        Ok(code.to_uppercase())
+    }
+}
+
+// Cloning takes a snapshot; the copy evolves independently of the original
+impl Clone for CurrencyRegistry {
+    fn clone(&self) -> Self {
+        Self {
+            currencies: RwLock::new(self.currencies.read().unwrap().clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::currency_unit::CurrencyUnit;
+
+    fn loyalty_points() -> Currency {
+        Currency::new(CurrencyUnit::new("PTS", 0, 0, "Loyalty Points"), "pts")
+    }
+
+    #[test]
+    fn test_register_and_lookup() {
+        let registry = CurrencyRegistry::new();
+        assert!(registry.is_empty());
+
+        registry.register(loyalty_points()).unwrap();
+        assert_eq!(registry.get("pts"), Some(loyalty_points()));
+        assert_eq!(registry.find_by_symbol("pts"), vec![loyalty_points()]);
+        assert_eq!(registry.get_by_numeric(0), None);
+
+        let err = registry.register(loyalty_points()).unwrap_err();
+        assert_eq!(err, CurrencyError::duplicate_currency("PTS"));
+
+        assert_eq!(registry.unregister("PTS"), Some(loyalty_points()));
+        assert!(!registry.contains("PTS"));
+        assert!(registry.register(Currency::new(CurrencyUnit::new("P-1", 0, 0, "Bad"), "p")).is_err());
+        assert!(registry.register_or_replace(Currency::new(CurrencyUnit::new("P-1", 0, 0, "Bad"), "p")).is_err());
+        assert!(!registry.contains("P-1"));
+
+        assert_eq!(registry.register_or_replace(loyalty_points()), Ok(None));
+        assert_eq!(registry.register_or_replace(loyalty_points()), Ok(Some(loyalty_points())));
+    }
+
+    #[test]
+    fn test_scoped_registry_is_isolated() {
+        let scoped = CurrencyRegistry::global().clone();
+        scoped.register(loyalty_points()).unwrap();
+
+        assert!(scoped.contains("PTS"));
+        assert!(scoped.contains("USD"));
+        assert!(!CurrencyRegistry::global().contains("PTS"));
+        assert_eq!(scoped.len(), CurrencyRegistry::global().len() + 1);
+    }
+
+    #[test]
+    fn test_symbol_lookup_is_ordered() {
+        let registry = CurrencyRegistry::with_defaults();
+        let codes: Vec<String> = registry
+            .find_by_symbol("kr")
+            .iter()
+            .map(|c| c.code().to_string())
+            .collect();
+        let mut sorted = codes.clone();
+        sorted.sort();
+        assert!(codes.len() > 1);
+        assert_eq!(codes, sorted);
+    }
+}
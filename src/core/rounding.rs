@@ -0,0 +1,250 @@
+// Rounding
+// ========
+//
+// One rounding engine for every numeric backend. Values are reduced to an integer
+// mantissa and a decimal scale and rounded on the exact remainder, so the eight
+// `RoundingMode`s behave the same for Decimal, f64, BigDecimal and MinorUnits, at any
+// scale. Negative scales round to tens, hundreds, and so on.
+
+use std::cmp::Ordering;
+use rust_decimal::Decimal;
+use crate::constants::RoundingMode;
+use crate::core::{Monetizable, MoneyError};
+
+/// Round `value` to `scale` decimal places. Values that already fit are returned as-is.
+pub fn round_decimal(value: Decimal, scale: i32, rounding_mode: &RoundingMode) -> Result<Decimal, MoneyError> {
+    let (units, scale) = round_units(value.mantissa(), value.scale() as i32, scale, rounding_mode)?;
+    Decimal::try_from_i128_with_scale(units, scale as u32)
+        .map_err(|_| MoneyError::Overflow(format!("rounding {} to scale {}", value, scale)))
+}
+
+/// Round `value` to `scale` decimal places, treating it as the decimal it prints as
+/// (2.675 rounds half-up to 2.68, not to 2.67 as its binary expansion would).
+pub fn round_f64(value: f64, scale: i32, rounding_mode: &RoundingMode) -> Result<f64, MoneyError> {
+    match Decimal::try_from_f64(value) {
+        Ok(decimal) => round_decimal(decimal, scale, rounding_mode)?.try_to_f64(),
+        // Finite floats beyond Decimal's range have no fractional digits left to round
+        Err(_) if value.is_finite() && scale >= 0 => Ok(value),
+        Err(err) => Err(err),
+    }
+}
+
+/// Round `unscaled × 10^-scale` down to `target_scale` digits. Returns the new mantissa
+/// and its scale, which is never negative: rounding to tens yields a scale-0 multiple of ten.
+/// Values with `target_scale` or fewer digits are returned unchanged.
+pub(crate) fn round_units(
+    unscaled: i128,
+    scale: i32,
+    target_scale: i32,
+    rounding_mode: &RoundingMode,
+) -> Result<(i128, i32), MoneyError> {
+    if target_scale >= scale {
+        return Ok((unscaled, scale));
+    }
+    let rounded = rescale(unscaled, scale, target_scale, rounding_mode)?;
+    if target_scale >= 0 {
+        Ok((rounded, target_scale))
+    } else {
+        Ok((rescale(rounded, target_scale, 0, rounding_mode)?, 0))
+    }
+}
+
+/// Express `unscaled × 10^-scale` with `target_scale` digits, rounding when digits are
+/// dropped and failing if the result no longer fits in an i128
+pub(crate) fn rescale(
+    unscaled: i128,
+    scale: i32,
+    target_scale: i32,
+    rounding_mode: &RoundingMode,
+) -> Result<i128, MoneyError> {
+    let difference = target_scale as i64 - scale as i64;
+    if difference >= 0 {
+        if unscaled == 0 {
+            return Ok(0);
+        }
+        return pow10(difference as u64)?
+            .checked_mul(unscaled)
+            .ok_or_else(|| MoneyError::Overflow(format!("rescaling to scale {}", target_scale)));
+    }
+
+    match pow10(difference.unsigned_abs()) {
+        Ok(divisor) => round_quotient(unscaled, divisor, rounding_mode),
+        // The divisor exceeds every i128, so the exact quotient is strictly between -1/2 and 1/2
+        Err(_) => round_quotient(unscaled.signum(), i128::MAX, rounding_mode),
+    }
+}
+
+/// 10^exponent, or `ScaleOutOfRange` once it no longer fits in an i128
+pub(crate) fn pow10(exponent: u64) -> Result<i128, MoneyError> {
+    u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| 10_i128.checked_pow(exponent))
+        .ok_or(MoneyError::ScaleOutOfRange(exponent as i64))
+}
+
+/// dividend / divisor rounded to an integer with `rounding_mode`, using the exact remainder
+pub(crate) fn round_quotient(dividend: i128, divisor: i128, rounding_mode: &RoundingMode) -> Result<i128, MoneyError> {
+    if divisor == 0 {
+        return Err(MoneyError::DivisionByZero);
+    }
+    let quotient = dividend / divisor;
+    let remainder = dividend % divisor;
+    if remainder == 0 {
+        return Ok(quotient);
+    }
+
+    let positive = (dividend < 0) == (divisor < 0);
+    // Compare the discarded fraction with one half without overflowing
+    let twice_remainder = remainder.unsigned_abs().saturating_mul(2);
+    let half = twice_remainder.cmp(&divisor.unsigned_abs());
+
+    let away_from_zero = match rounding_mode {
+        RoundingMode::Up => true,
+        RoundingMode::Down => false,
+        RoundingMode::Ceiling => positive,
+        RoundingMode::Floor => !positive,
+        RoundingMode::HalfUp => half != Ordering::Less,
+        RoundingMode::HalfDown => half == Ordering::Greater,
+        RoundingMode::HalfEven => half == Ordering::Greater || (half == Ordering::Equal && quotient % 2 != 0),
+        RoundingMode::Unnecessary => return Err(MoneyError::PrecisionLoss),
+    };
+
+    Ok(match (away_from_zero, positive) {
+        (false, _) => quotient,
+        (true, true) => quotient + 1,
+        (true, false) => quotient - 1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use proptest::prelude::*;
+    use rust_decimal::RoundingStrategy;
+    use crate::core::types::BigDecimal;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    /// Straightforward floor/ceiling formulation of each mode
+    fn reference(value: i128, divisor: i128, mode: RoundingMode) -> Option<i128> {
+        let floor = value.div_euclid(divisor);
+        let remainder = value.rem_euclid(divisor);
+        if remainder == 0 {
+            return Some(floor);
+        }
+        let ceiling = floor + 1;
+        let (toward_zero, away_from_zero) = if value >= 0 { (floor, ceiling) } else { (ceiling, floor) };
+        Some(match mode {
+            RoundingMode::Up => away_from_zero,
+            RoundingMode::Down => toward_zero,
+            RoundingMode::Ceiling => ceiling,
+            RoundingMode::Floor => floor,
+            RoundingMode::Unnecessary => return None,
+            _ => match (2 * remainder).cmp(&divisor) {
+                Ordering::Less => floor,
+                Ordering::Greater => ceiling,
+                Ordering::Equal => match mode {
+                    RoundingMode::HalfUp => away_from_zero,
+                    RoundingMode::HalfDown => toward_zero,
+                    _ if floor % 2 == 0 => floor,
+                    _ => ceiling,
+                },
+            },
+        })
+    }
+
+    fn strategy(mode: RoundingMode) -> Option<RoundingStrategy> {
+        Some(match mode {
+            RoundingMode::Up => RoundingStrategy::AwayFromZero,
+            RoundingMode::Down => RoundingStrategy::ToZero,
+            RoundingMode::Ceiling => RoundingStrategy::ToPositiveInfinity,
+            RoundingMode::Floor => RoundingStrategy::ToNegativeInfinity,
+            RoundingMode::HalfUp => RoundingStrategy::MidpointAwayFromZero,
+            RoundingMode::HalfDown => RoundingStrategy::MidpointTowardZero,
+            RoundingMode::HalfEven => RoundingStrategy::MidpointNearestEven,
+            RoundingMode::Unnecessary => return None,
+        })
+    }
+
+    fn any_mode() -> impl Strategy<Value = RoundingMode> {
+        proptest::sample::select(RoundingMode::ALL.to_vec())
+    }
+
+    #[test]
+    fn test_every_mode() {
+        let expected = [
+            (RoundingMode::Up, ["2.36", "-2.36", "2.35"]),
+            (RoundingMode::Down, ["2.35", "-2.35", "2.35"]),
+            (RoundingMode::Ceiling, ["2.36", "-2.35", "2.35"]),
+            (RoundingMode::Floor, ["2.35", "-2.36", "2.35"]),
+            (RoundingMode::HalfUp, ["2.36", "-2.36", "2.35"]),
+            (RoundingMode::HalfDown, ["2.35", "-2.35", "2.35"]),
+            (RoundingMode::HalfEven, ["2.36", "-2.36", "2.35"]),
+        ];
+        for (mode, [positive, negative, exact]) in expected {
+            assert_eq!(round_decimal(dec("2.355"), 2, &mode).unwrap(), dec(positive), "{}", mode);
+            assert_eq!(round_decimal(dec("-2.355"), 2, &mode).unwrap(), dec(negative), "{}", mode);
+            assert_eq!(round_decimal(dec("2.35"), 2, &mode).unwrap(), dec(exact), "{}", mode);
+        }
+
+        assert_eq!(round_decimal(dec("2.355"), 2, &RoundingMode::Unnecessary), Err(MoneyError::PrecisionLoss));
+        assert_eq!(round_decimal(dec("2.350"), 2, &RoundingMode::Unnecessary).unwrap(), dec("2.35"));
+        assert_eq!(round_f64(2.675, 2, &RoundingMode::HalfUp).unwrap(), 2.68);
+        assert_eq!(round_f64(-0.125, 2, &RoundingMode::HalfEven).unwrap(), -0.12);
+    }
+
+    #[test]
+    fn test_negative_scale() {
+        assert_eq!(round_decimal(dec("1250"), -2, &RoundingMode::HalfEven).unwrap(), dec("1200"));
+        assert_eq!(round_decimal(dec("1250.01"), -2, &RoundingMode::HalfEven).unwrap(), dec("1300"));
+        assert_eq!(round_decimal(dec("-1234.5"), -1, &RoundingMode::Floor).unwrap(), dec("-1240"));
+        assert_eq!(round_f64(98765.0, -3, &RoundingMode::Down).unwrap(), 98000.0);
+        assert_eq!(round_decimal(dec("5"), -40, &RoundingMode::Up).map_err(|_| ()), Err(()));
+        assert_eq!(round_decimal(dec("5"), -40, &RoundingMode::HalfUp).unwrap(), Decimal::ZERO);
+
+        let big = BigDecimal::from_i64(1250).try_with_scale(-2, &RoundingMode::HalfUp).unwrap();
+        assert_eq!(big.to_string(), "1300");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_reference(value in any::<i64>(), digits in 0u32..12, mode in any_mode()) {
+            let divisor = 10_i128.pow(digits);
+            let expected = reference(value as i128, divisor, mode);
+            let actual = round_quotient(value as i128, divisor, &mode).ok();
+            prop_assert_eq!(actual, expected);
+
+            let big = BigDecimal::new(value as i128, 6).try_with_scale(6 - digits as i32, &mode);
+            prop_assert_eq!(big.ok().map(|b| b.unscaled_value()), expected);
+        }
+
+        #[test]
+        fn prop_decimal_matches_rust_decimal(
+            mantissa in any::<i64>(),
+            scale in 0u32..16,
+            target in 0u32..16,
+            mode in any_mode(),
+        ) {
+            let value = Decimal::new(mantissa, scale);
+            let rounded = round_decimal(value, target as i32, &mode);
+            match strategy(mode) {
+                Some(strategy) => prop_assert_eq!(rounded.unwrap(), value.round_dp_with_strategy(target, strategy)),
+                None => prop_assert_eq!(rounded.is_ok(), value.round_dp(target) == value),
+            }
+        }
+
+        #[test]
+        fn prop_f64_stays_within_one_unit(value in -1.0e9f64..1.0e9, scale in 0i32..6, mode in any_mode()) {
+            prop_assume!(mode != RoundingMode::Unnecessary);
+            let rounded = round_f64(value, scale, &mode).unwrap();
+            // One unit at `scale`, plus float spacing at this magnitude; the range keeps
+            // results within the 15 significant digits an f64 round-trips
+            let tolerance = 10f64.powi(-scale) + value.abs() * f64::EPSILON * 4.0;
+            prop_assert!((rounded - value).abs() <= tolerance);
+            prop_assert_eq!(round_f64(rounded, scale, &RoundingMode::Unnecessary).unwrap(), rounded);
+        }
+    }
+}
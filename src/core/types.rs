@@ -2,6 +2,8 @@
 use std::cmp::{Ordering, PartialEq, PartialOrd};
 use std::fmt;
 use crate::constants::RoundingMode;
+use crate::core::MoneyError;
+use crate::core::rounding::{pow10, rescale, round_quotient};
 use std::str::FromStr;
 
 // Simplified BigDecimal implementation for demonstration
@@ -27,13 +29,30 @@ impl BigDecimal {
         }
     }
 
+    /// `value` rounded to `scale` decimal places.
+    ///
+    /// Panics when the value does not fit or the scale is out of range; use
+    /// `try_from_f64_with_scale` to get an error instead.
     pub fn from_f64(value: f64, scale: i32) -> Self {
-        let factor = 10_i128.pow(scale as u32);
-        let unscaled_value = (value * factor as f64).round() as i128;
-        Self {
-            unscaled_value,
-            scale,
+        Self::try_from_f64_with_scale(value, scale).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// `value` rounded to `scale` decimal places, reporting non-finite values,
+    /// unreachable scales and values beyond the i128 range as errors
+    pub fn try_from_f64_with_scale(value: f64, scale: i32) -> Result<Self, MoneyError> {
+        if !value.is_finite() {
+            return Err(MoneyError::ConversionError(format!("Invalid float value: {}", value)));
         }
+        let factor = pow10(scale.unsigned_abs() as u64)? as f64;
+        let scaled = if scale >= 0 { value * factor } else { value / factor }.round();
+        // i128::MAX as f64 rounds up to 2^127, which is already out of range
+        if !(scaled >= i128::MIN as f64 && scaled < i128::MAX as f64) {
+            return Err(MoneyError::Overflow(format!("{} at scale {}", value, scale)));
+        }
+        Ok(Self {
+            unscaled_value: scaled as i128,
+            scale,
+        })
     }
 
     pub fn zero() -> Self {
@@ -63,153 +82,114 @@ impl BigDecimal {
         self.unscaled_value as f64 / divisor
     }
 
+    /// Rescale with rounding.
+    ///
+    /// Panics when the result overflows or when `RoundingMode::Unnecessary` would have
+    /// to round; use `try_with_scale` to get an error instead.
     pub fn with_scale(&self, scale: i32, rounding_mode: &RoundingMode) -> Self {
-        if scale == self.scale {
-            return self.clone();
-        }
-
-        if scale > self.scale {
-            // Increase precision (no rounding needed)
-            let factor = 10_i128.pow((scale - self.scale) as u32);
-            Self {
-                unscaled_value: self.unscaled_value * factor,
-                scale,
+        match self.try_with_scale(scale, rounding_mode) {
+            Ok(value) => value,
+            Err(MoneyError::PrecisionLoss) => {
+                panic!("Rounding necessary but RoundingMode::Unnecessary specified")
             }
-        } else {
-            // Decrease precision (rounding needed)
-            let factor = 10_i128.pow((self.scale - scale) as u32);
-            let unscaled_value = match rounding_mode {
-                RoundingMode::HalfEven => {
-                    // Banker's rounding (round to even)
-                    let remainder = self.unscaled_value % factor;
-                    let half = factor / 2;
-                    
-                    let quotient = self.unscaled_value / factor;
-                    if remainder.abs() > half || (remainder.abs() == half && quotient % 2 != 0) {
-                        quotient + if remainder >= 0 { 1 } else { -1 }
-                    } else {
-                        quotient
-                    }
-                }
-                RoundingMode::HalfUp => {
-                    let remainder = self.unscaled_value % factor;
-                    let half = factor / 2;
-                    let quotient = self.unscaled_value / factor;
-                    
-                    if remainder.abs() >= half {
-                        quotient + if remainder >= 0 { 1 } else { -1 }
-                    } else {
-                        quotient
-                    }
-                }
-                RoundingMode::HalfDown => {
-                    let remainder = self.unscaled_value % factor;
-                    let half = factor / 2;
-                    let quotient = self.unscaled_value / factor;
-                    
-                    if remainder.abs() > half {
-                        quotient + if remainder >= 0 { 1 } else { -1 }
-                    } else {
-                        quotient
-                    }
-                }
-                RoundingMode::Ceiling => {
-                    let quotient = self.unscaled_value / factor;
-                    let remainder = self.unscaled_value % factor;
-                    if remainder > 0 {
-                        quotient + 1
-                    } else {
-                        quotient
-                    }
-                }
-                RoundingMode::Floor => {
-                    let quotient = self.unscaled_value / factor;
-                    let remainder = self.unscaled_value % factor;
-                    if remainder < 0 {
-                        quotient - 1
-                    } else {
-                        quotient
-                    }
-                }
-                RoundingMode::Down => self.unscaled_value / factor,
-                RoundingMode::Up => {
-                    let quotient = self.unscaled_value / factor;
-                    let remainder = self.unscaled_value % factor;
-                    if remainder != 0 {
-                        quotient + if remainder > 0 { 1 } else { -1 }
-                    } else {
-                        quotient
-                    }
-                }
-                RoundingMode::Unnecessary => {
-                    if self.unscaled_value % factor != 0 {
-                        panic!("Rounding necessary but RoundingMode::Unnecessary specified");
-                    }
-                    self.unscaled_value / factor
-                }
-            };
+            Err(err) => panic!("{}", err),
+        }
+    }
 
-            Self {
-                unscaled_value,
-                scale,
-            }
+    /// Rescale with rounding, reporting overflow, unreachable scales and rounding
+    /// required under `RoundingMode::Unnecessary` (`PrecisionLoss`) as errors
+    pub fn try_with_scale(&self, scale: i32, rounding_mode: &RoundingMode) -> Result<Self, MoneyError> {
+        if scale == self.scale {
+            return Ok(*self);
         }
+
+        Ok(Self {
+            unscaled_value: rescale(self.unscaled_value, self.scale, scale, rounding_mode)?,
+            scale,
+        })
     }
 
-    pub fn add(&self, other: &Self, rounding_mode: &RoundingMode) -> Self {
+    pub fn add(&self, other: &Self, rounding_mode: &RoundingMode) -> Result<Self, MoneyError> {
         // Ensure both numbers have the same scale for addition
         let max_scale = self.scale.max(other.scale);
-        let self_adjusted = self.with_scale(max_scale, rounding_mode);
-        let other_adjusted = other.with_scale(max_scale, rounding_mode);
-        
-        Self {
-            unscaled_value: self_adjusted.unscaled_value + other_adjusted.unscaled_value,
+        let self_adjusted = self.try_with_scale(max_scale, rounding_mode)?;
+        let other_adjusted = other.try_with_scale(max_scale, rounding_mode)?;
+
+        let unscaled_value = self_adjusted
+            .unscaled_value
+            .checked_add(other_adjusted.unscaled_value)
+            .ok_or_else(|| MoneyError::Overflow(format!("{} + {}", self, other)))?;
+        Ok(Self {
+            unscaled_value,
             scale: max_scale,
-        }
+        })
     }
 
-    pub fn subtract(&self, other: &Self, rounding_mode: &RoundingMode) -> Self {
+    pub fn subtract(&self, other: &Self, rounding_mode: &RoundingMode) -> Result<Self, MoneyError> {
         // Ensure both numbers have the same scale for subtraction
         let max_scale = self.scale.max(other.scale);
-        let self_adjusted = self.with_scale(max_scale, rounding_mode);
-        let other_adjusted = other.with_scale(max_scale, rounding_mode);
-        
-        Self {
-            unscaled_value: self_adjusted.unscaled_value - other_adjusted.unscaled_value,
+        let self_adjusted = self.try_with_scale(max_scale, rounding_mode)?;
+        let other_adjusted = other.try_with_scale(max_scale, rounding_mode)?;
+
+        let unscaled_value = self_adjusted
+            .unscaled_value
+            .checked_sub(other_adjusted.unscaled_value)
+            .ok_or_else(|| MoneyError::Overflow(format!("{} - {}", self, other)))?;
+        Ok(Self {
+            unscaled_value,
             scale: max_scale,
-        }
+        })
     }
 
-    pub fn multiply(&self, other: &Self, rounding_mode: &RoundingMode, target_scale: i32) -> Self {
+    pub fn multiply(&self, other: &Self, rounding_mode: &RoundingMode, target_scale: i32) -> Result<Self, MoneyError> {
         // When multiplying, scales add up
-        let result_scale = self.scale + other.scale;
+        let result_scale = self
+            .scale
+            .checked_add(other.scale)
+            .ok_or(MoneyError::ScaleOutOfRange(self.scale as i64 + other.scale as i64))?;
+        let unscaled_value = self
+            .unscaled_value
+            .checked_mul(other.unscaled_value)
+            .ok_or_else(|| MoneyError::Overflow(format!("{} * {}", self, other)))?;
         let result = Self {
-            unscaled_value: self.unscaled_value * other.unscaled_value,
+            unscaled_value,
             scale: result_scale,
         };
-        
+
         // Adjust to target scale if needed
-        result.with_scale(target_scale, rounding_mode)
+        result.try_with_scale(target_scale, rounding_mode)
     }
 
-    pub fn divide(&self, other: &Self, rounding_mode: &RoundingMode, target_scale: i32) -> Result<Self, &'static str> {
+    /// Divide, rounding the exact quotient to `target_scale` with `rounding_mode`
+    pub fn divide(&self, other: &Self, rounding_mode: &RoundingMode, target_scale: i32) -> Result<Self, MoneyError> {
         if other.unscaled_value == 0 {
-            return Err("Division by zero");
+            return Err(MoneyError::DivisionByZero);
         }
 
-        // For better precision, scale up the dividend
-        let scale_factor = 10_i128.pow((target_scale + 10) as u32);
-        let scaled_dividend = self.unscaled_value * scale_factor;
-        let quotient = scaled_dividend / other.unscaled_value;
-        
-        let result = Self {
-            unscaled_value: quotient,
-            scale: self.scale - other.scale + target_scale + 10,
+        // a / b at target scale t is (ua * 10^(t - sa + sb)) / ub; a negative exponent
+        // moves the power of ten to the divisor instead
+        let exponent = target_scale as i64 - self.scale as i64 + other.scale as i64;
+        let overflow = || MoneyError::Overflow(format!("{} / {}", self, other));
+        let (dividend, divisor) = if exponent >= 0 {
+            let dividend = self
+                .unscaled_value
+                .checked_mul(pow10(exponent as u64)?)
+                .ok_or_else(overflow)?;
+            (dividend, other.unscaled_value)
+        } else {
+            match pow10(exponent.unsigned_abs()).ok().and_then(|f| other.unscaled_value.checked_mul(f)) {
+                Some(divisor) => (self.unscaled_value, divisor),
+                // The divisor exceeds any i128 dividend: the exact quotient is below one unit,
+                // so only its sign matters for rounding
+                None => (self.unscaled_value.signum() * other.unscaled_value.signum(), i128::MAX),
+            }
         };
-        
-        Ok(result.with_scale(target_scale, rounding_mode))
-    }
 
+        Ok(Self {
+            unscaled_value: round_quotient(dividend, divisor, rounding_mode)?,
+            scale: target_scale,
+        })
+    }
     pub fn negate(&self) -> Self {
         Self {
             unscaled_value: -self.unscaled_value,
@@ -399,7 +379,7 @@ mod tests {
         let bd2 = BigDecimal::from_i64(42);
         assert_eq!(bd2.to_string(), "42");
 
-        let bd3 = BigDecimal::from_f64(3.14159, 5);
+        let bd3 = BigDecimal::from_f64(1.23456, 5);
         assert_eq!(bd3.scale(), 5);
     }
 
@@ -486,15 +466,15 @@ mod tests {
         let bd2 = BigDecimal::from_str("67.89").unwrap();
         
         // Addition
-        let sum = bd1.add(&bd2, &RoundingMode::HalfEven);
+        let sum = bd1.add(&bd2, &RoundingMode::HalfEven).unwrap();
         assert_eq!(sum.to_string(), "191.34");
         
         // Subtraction
-        let diff = bd1.subtract(&bd2, &RoundingMode::HalfEven);
+        let diff = bd1.subtract(&bd2, &RoundingMode::HalfEven).unwrap();
         assert_eq!(diff.to_string(), "55.56");
         
         // Multiplication: 123.45 * 67.89 = 8381.0205, rounded to 2 decimals = 8381.02
-        let product = bd1.multiply(&bd2, &RoundingMode::HalfEven, 2);
+        let product = bd1.multiply(&bd2, &RoundingMode::HalfEven, 2).unwrap();
         assert_eq!(product.to_string(), "8381.02");
         
         // Division: 123.45 / 67.89 ≈ 1.8183826778612462, rounded to 2 decimals = 1.82
@@ -608,7 +588,7 @@ mod tests {
         // Test arithmetic with different scales
         let bd1 = BigDecimal::new(123, 1); // 12.3
         let bd2 = BigDecimal::new(4567, 3); // 4.567
-        let sum = bd1.add(&bd2, &RoundingMode::HalfEven);
+        let sum = bd1.add(&bd2, &RoundingMode::HalfEven).unwrap();
         assert_eq!(sum.to_string(), "16.867");
     }
 
@@ -618,10 +598,10 @@ mod tests {
         let bd1 = BigDecimal::from_str("1.23456789").unwrap();
         let bd2 = BigDecimal::from_str("9.87654321").unwrap();
         
-        let sum = bd1.add(&bd2, &RoundingMode::HalfEven);
+        let sum = bd1.add(&bd2, &RoundingMode::HalfEven).unwrap();
         assert_eq!(sum.to_string(), "11.11111110");
         
-        let product = bd1.multiply(&bd2, &RoundingMode::HalfEven, 8);
+        let product = bd1.multiply(&bd2, &RoundingMode::HalfEven, 8).unwrap();
         assert_eq!(product.to_string(), "12.19326311");
     }
 
@@ -632,13 +612,66 @@ mod tests {
         let bd1 = BigDecimal::new(12345, 2); // 123.45
         let bd2 = BigDecimal::new(6789, 3);  // 6.789
         
-        let sum = bd1.add(&bd2, &RoundingMode::HalfEven);
+        let sum = bd1.add(&bd2, &RoundingMode::HalfEven).unwrap();
         assert_eq!(sum.scale(), 3); // Should use the higher scale
         assert_eq!(sum.to_string(), "130.239");
         
-        let product = bd1.multiply(&bd2, &RoundingMode::HalfEven, 4);
+        let product = bd1.multiply(&bd2, &RoundingMode::HalfEven, 4).unwrap();
         assert_eq!(product.scale(), 4);
-        assert_eq!(product.to_string(), "838.4121"); // 123.45 * 6.789 = 838.41205, rounded to 4 decimals
+        assert_eq!(product.to_string(), "838.1020"); // 123.45 * 6.789 = 838.10205, rounded to 4 decimals (half-even)
+    }
+
+
+    #[test]
+    fn test_checked_results() {
+        let max = BigDecimal::new(i128::MAX, 0);
+        assert!(matches!(max.add(&BigDecimal::from_i64(1), &RoundingMode::HalfEven), Err(MoneyError::Overflow(_))));
+        assert!(matches!(max.try_with_scale(2, &RoundingMode::HalfEven), Err(MoneyError::Overflow(_))));
+        assert!(matches!(
+            BigDecimal::new(1, 0).try_with_scale(40, &RoundingMode::HalfEven),
+            Err(MoneyError::ScaleOutOfRange(40))
+        ));
+        assert_eq!(
+            BigDecimal::new(125, 2).try_with_scale(1, &RoundingMode::Unnecessary),
+            Err(MoneyError::PrecisionLoss)
+        );
+
+        // Large target scales no longer overflow an intermediate power of ten
+        let third = BigDecimal::from_i64(1).divide(&BigDecimal::from_i64(3), &RoundingMode::HalfEven, 30).unwrap();
+        assert_eq!(third.to_string(), "0.333333333333333333333333333333");
+        let tiny = BigDecimal::new(1, 30).divide(&BigDecimal::new(3, 0), &RoundingMode::Up, 2).unwrap();
+        assert_eq!(tiny.to_string(), "0.01");
+        assert_eq!(
+            BigDecimal::from_i64(-7).divide(&BigDecimal::from_i64(2), &RoundingMode::HalfEven, 0).unwrap().to_string(),
+            "-4"
+        );
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn test_divide_tiny_by_negative_keeps_sign() {
+        let tiny = BigDecimal::new(1, 40);
+        let minus_two = BigDecimal::from_i64(-2);
+        let quotient = |value: &BigDecimal, mode| value.divide(&minus_two, &mode, 0).unwrap().to_string();
+
+        assert_eq!(quotient(&tiny, RoundingMode::Floor), "-1");
+        assert_eq!(quotient(&tiny, RoundingMode::Up), "-1");
+        assert_eq!(quotient(&tiny, RoundingMode::Ceiling), "0");
+        assert_eq!(quotient(&tiny, RoundingMode::Down), "0");
+
+        let negative_tiny = tiny.negate();
+        assert_eq!(quotient(&negative_tiny, RoundingMode::Floor), "0");
+        assert_eq!(quotient(&negative_tiny, RoundingMode::Ceiling), "1");
+        assert_eq!(quotient(&negative_tiny, RoundingMode::Up), "1");
+    }
+
+    #[test]
+    fn test_from_f64_is_checked() {
+        assert_eq!(BigDecimal::try_from_f64_with_scale(12.345, 2).unwrap().to_string(), "12.35");
+        assert_eq!(BigDecimal::try_from_f64_with_scale(1250.0, -2).unwrap(), BigDecimal::new(13, -2));
+        assert_eq!(BigDecimal::try_from_f64_with_scale(1.0, 39), Err(MoneyError::ScaleOutOfRange(39)));
+        assert!(matches!(BigDecimal::try_from_f64_with_scale(1e300, 8), Err(MoneyError::Overflow(_))));
+        assert!(matches!(<BigDecimal as crate::core::Monetizable>::try_from_f64(1e300), Err(MoneyError::Overflow(_))));
+        assert!(matches!(<BigDecimal as crate::core::Monetizable>::try_from_f32(3e38), Err(MoneyError::Overflow(_))));
+        assert_eq!(<BigDecimal as crate::core::Monetizable>::try_from_f64(1.5).unwrap().to_string(), "1.5");
+    }
+}
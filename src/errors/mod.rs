@@ -1,4 +1,89 @@
 use std::{error, fmt};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+
+/// Errors from amount arithmetic, rounding and numeric conversion
+#[derive(Debug, Clone, PartialEq)]
+pub enum MoneyError {
+    ConversionError(String),
+    /// Codes of the two currencies: the operation's own, then the other operand's
+    CurrencyMismatch(String, String),
+    InvalidExchangeRate(f64),
+    PrecisionLoss,
+    InvalidAllocation(String),
+    Overflow(String),
+    DivisionByZero,
+    ScaleOutOfRange(i64),
+    /// An aggregate (named by the operation) was asked of no amounts
+    EmptyAggregate(String),
+}
+
+impl MoneyError {
+    /// Get the error category as a string
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::ConversionError(_) => "ConversionError",
+            Self::CurrencyMismatch(..) => "CurrencyMismatch",
+            Self::InvalidExchangeRate(_) => "InvalidExchangeRate",
+            Self::PrecisionLoss => "PrecisionLoss",
+            Self::InvalidAllocation(_) => "InvalidAllocation",
+            Self::Overflow(_) => "Overflow",
+            Self::DivisionByZero => "DivisionByZero",
+            Self::ScaleOutOfRange(_) => "ScaleOutOfRange",
+            Self::EmptyAggregate(_) => "EmptyAggregate",
+        }
+    }
+
+    /// Check if this is a recoverable error
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::PrecisionLoss => true, // Retry with a rounding mode other than Unnecessary
+            Self::ConversionError(_)
+            | Self::CurrencyMismatch(..)
+            | Self::InvalidExchangeRate(_)
+            | Self::InvalidAllocation(_)
+            | Self::Overflow(_)
+            | Self::DivisionByZero
+            | Self::ScaleOutOfRange(_)
+            | Self::EmptyAggregate(_) => false,
+        }
+    }
+
+    /// Stable machine-readable code, see `Error::code`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::ConversionError(_) => "money.conversion_error",
+            Self::CurrencyMismatch(..) => "money.currency_mismatch",
+            Self::InvalidExchangeRate(_) => "money.invalid_exchange_rate",
+            Self::PrecisionLoss => "money.precision_loss",
+            Self::InvalidAllocation(_) => "money.invalid_allocation",
+            Self::Overflow(_) => "money.overflow",
+            Self::DivisionByZero => "money.division_by_zero",
+            Self::ScaleOutOfRange(_) => "money.scale_out_of_range",
+            Self::EmptyAggregate(_) => "money.empty_aggregate",
+        }
+    }
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::ConversionError(msg) => write!(f, "Conversion error: {}", msg),
+            MoneyError::CurrencyMismatch(expected, actual) => {
+                write!(f, "Currency mismatch: expected '{}', got '{}'", expected, actual)
+            }
+            MoneyError::InvalidExchangeRate(rate) => write!(f, "Invalid exchange rate: {}", rate),
+            MoneyError::PrecisionLoss => write!(f, "Precision loss in conversion"),
+            MoneyError::InvalidAllocation(reason) => write!(f, "Invalid allocation: {}", reason),
+            MoneyError::Overflow(operation) => write!(f, "Arithmetic overflow in {}", operation),
+            MoneyError::DivisionByZero => write!(f, "Division by zero"),
+            MoneyError::ScaleOutOfRange(scale) => write!(f, "Scale out of range: {}", scale),
+            MoneyError::EmptyAggregate(operation) => write!(f, "Cannot take the {} of no amounts", operation),
+        }
+    }
+}
+
+impl error::Error for MoneyError {}
 
 /// Currency-specific errors with enhanced functionality
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,6 +97,8 @@ pub enum CurrencyError {
     InvalidFormat { 
         message: String,
         input: Option<String>,
+        /// Byte offset into `input` where parsing failed
+        position: Option<usize>,
     },
     /// Currency mismatch in operations
     CurrencyMismatch { 
@@ -30,6 +117,10 @@ pub enum CurrencyError {
         amount: String,
         reason: String,
     },
+    /// A currency with the same code is already registered
+    DuplicateCurrency {
+        code: String,
+    },
 }
 
 impl CurrencyError {
@@ -54,6 +145,7 @@ impl CurrencyError {
         Self::InvalidFormat {
             message: message.into(),
             input: None,
+            position: None,
         }
     }
 
@@ -62,6 +154,16 @@ impl CurrencyError {
         Self::InvalidFormat {
             message: message.into(),
             input: Some(input.into()),
+            position: None,
+        }
+    }
+
+    /// Create a new InvalidFormat error pointing at a byte offset in the input
+    pub fn invalid_format_at(message: impl Into<String>, input: impl Into<String>, position: usize) -> Self {
+        Self::InvalidFormat {
+            message: message.into(),
+            input: Some(input.into()),
+            position: Some(position),
         }
     }
 
@@ -108,6 +210,11 @@ impl CurrencyError {
         }
     }
 
+    /// Create a new DuplicateCurrency error
+    pub fn duplicate_currency(code: impl Into<String>) -> Self {
+        Self::DuplicateCurrency { code: code.into() }
+    }
+
     /// Get the error category as a string
     pub fn category(&self) -> &'static str {
         match self {
@@ -116,6 +223,7 @@ impl CurrencyError {
             Self::CurrencyMismatch { .. } => "CurrencyMismatch",
             Self::ConversionError { .. } => "ConversionError",
             Self::InvalidAmount { .. } => "InvalidAmount",
+            Self::DuplicateCurrency { .. } => "DuplicateCurrency",
         }
     }
 
@@ -127,6 +235,19 @@ impl CurrencyError {
             Self::CurrencyMismatch { .. } => false,
             Self::ConversionError { .. } => true, // Might retry with different rates
             Self::InvalidAmount { .. } => false,
+            Self::DuplicateCurrency { .. } => false,
+        }
+    }
+
+    /// Stable machine-readable code, see `Error::code`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownCurrency { .. } => "currency.unknown_currency",
+            Self::InvalidFormat { .. } => "currency.invalid_format",
+            Self::CurrencyMismatch { .. } => "currency.currency_mismatch",
+            Self::ConversionError { .. } => "currency.conversion_error",
+            Self::InvalidAmount { .. } => "currency.invalid_amount",
+            Self::DuplicateCurrency { .. } => "currency.duplicate_currency",
         }
     }
 }
@@ -141,11 +262,13 @@ impl fmt::Display for CurrencyError {
                     write!(f, "Unknown currency code: {}", code)
                 }
             }
-            Self::InvalidFormat { message, input } => {
-                if let Some(inp) = input {
-                    write!(f, "Invalid currency format: {} (input: '{}')", message, inp)
-                } else {
-                    write!(f, "Invalid currency format: {}", message)
+            Self::InvalidFormat { message, input, position } => {
+                match (input, position) {
+                    (Some(inp), Some(pos)) => {
+                        write!(f, "Invalid currency format: {} at byte {} (input: '{}')", message, pos, inp)
+                    }
+                    (Some(inp), None) => write!(f, "Invalid currency format: {} (input: '{}')", message, inp),
+                    _ => write!(f, "Invalid currency format: {}", message),
                 }
             }
             Self::CurrencyMismatch { expected, actual, operation } => {
@@ -161,6 +284,9 @@ impl fmt::Display for CurrencyError {
             Self::InvalidAmount { amount, reason } => {
                 write!(f, "Invalid currency amount '{}': {}", amount, reason)
             }
+            Self::DuplicateCurrency { code } => {
+                write!(f, "Currency '{}' is already registered", code)
+            }
         }
     }
 }
@@ -175,6 +301,472 @@ impl error::Error for CurrencyError {
 // Convenience type alias for Results
 pub type CurrencyResult<T> = Result<T, CurrencyError>;
 
+/// Errors raised while loading a `MonetaryConfig`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The configuration file could not be read
+    Io {
+        path: String,
+        message: String,
+    },
+    /// The configuration document is malformed
+    Parse {
+        format: String,
+        message: String,
+    },
+    /// The file extension does not map to a supported format
+    UnsupportedFormat {
+        path: String,
+    },
+    /// A setting has a value that cannot be used
+    InvalidValue {
+        key: String,
+        value: String,
+        reason: String,
+    },
+}
+
+impl ConfigError {
+    /// Create a new Io error
+    pub fn io(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Io {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a new Parse error
+    pub fn parse(format: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Parse {
+            format: format.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a new UnsupportedFormat error
+    pub fn unsupported_format(path: impl Into<String>) -> Self {
+        Self::UnsupportedFormat { path: path.into() }
+    }
+
+    /// Create a new InvalidValue error
+    pub fn invalid_value(key: impl Into<String>, value: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::InvalidValue {
+            key: key.into(),
+            value: value.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Get the error category as a string
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Io { .. } => "Io",
+            Self::Parse { .. } => "Parse",
+            Self::UnsupportedFormat { .. } => "UnsupportedFormat",
+            Self::InvalidValue { .. } => "InvalidValue",
+        }
+    }
+
+    /// Check if this is a recoverable error
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Io { .. } => true, // The file may appear or become readable
+            Self::Parse { .. } => false,
+            Self::UnsupportedFormat { .. } => false,
+            Self::InvalidValue { .. } => false,
+        }
+    }
+
+    /// Stable machine-readable code, see `Error::code`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Io { .. } => "config.io",
+            Self::Parse { .. } => "config.parse",
+            Self::UnsupportedFormat { .. } => "config.unsupported_format",
+            Self::InvalidValue { .. } => "config.invalid_value",
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, message } => write!(f, "Cannot read config file '{}': {}", path, message),
+            Self::Parse { format, message } => write!(f, "Invalid {} config: {}", format, message),
+            Self::UnsupportedFormat { path } => {
+                write!(f, "Unsupported config format for '{}' (expected .toml or .json)", path)
+            }
+            Self::InvalidValue { key, value, reason } => {
+                write!(f, "Invalid config value '{}' for '{}': {}", value, key, reason)
+            }
+        }
+    }
+}
+
+impl error::Error for ConfigError {}
+
+
+
+
+
+/// Errors raised while looking up exchange rates and converting amounts. Every variant
+/// names the currency pair involved so a failure can be traced to its source.
+#[derive(Debug, Clone)]
+pub enum ExchangeError {
+    /// The rate does not convert from the amount's currency
+    CurrencyMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// No provider quotes the pair
+    NoRateFound {
+        base: String,
+        target: String,
+    },
+    /// The rate outlived its time-to-live
+    ExpiredRate {
+        base: String,
+        target: String,
+        expired_at: DateTime<Utc>,
+    },
+    /// The rate cannot be used, e.g. a zero rate that cannot be inverted
+    InvalidRate {
+        base: String,
+        target: String,
+        reason: String,
+    },
+    /// A provider failed to answer, as opposed to not knowing the pair
+    ProviderError {
+        provider: String,
+        base: String,
+        target: String,
+        source: Arc<dyn error::Error + Send + Sync>,
+    },
+    /// Every provider was tried and at least one of them failed; holds each
+    /// provider's error in the order they were asked
+    AllProvidersFailed {
+        base: String,
+        target: String,
+        errors: Vec<ExchangeError>,
+    },
+    /// The converted amount could not be computed or represented
+    ConversionError {
+        base: String,
+        target: String,
+        source: MoneyError,
+    },
+    /// A currency code is not registered
+    UnknownCurrency {
+        code: String,
+    },
+}
+
+impl ExchangeError {
+    /// Create a new CurrencyMismatch error
+    pub fn currency_mismatch(expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Self::CurrencyMismatch {
+            expected: expected.into(),
+            actual: actual.into(),
+        }
+    }
+
+    /// Create a new NoRateFound error
+    pub fn no_rate_found(base: impl Into<String>, target: impl Into<String>) -> Self {
+        Self::NoRateFound {
+            base: base.into(),
+            target: target.into(),
+        }
+    }
+
+    /// Create a new ExpiredRate error
+    pub fn expired_rate(base: impl Into<String>, target: impl Into<String>, expired_at: DateTime<Utc>) -> Self {
+        Self::ExpiredRate {
+            base: base.into(),
+            target: target.into(),
+            expired_at,
+        }
+    }
+
+    /// Create a new InvalidRate error
+    pub fn invalid_rate(base: impl Into<String>, target: impl Into<String>, reason: impl Into<String>) -> Self {
+        Self::InvalidRate {
+            base: base.into(),
+            target: target.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Create a new ProviderError wrapping the provider's own error
+    pub fn provider_error(
+        provider: impl Into<String>,
+        base: impl Into<String>,
+        target: impl Into<String>,
+        source: impl Into<Box<dyn error::Error + Send + Sync>>
+    ) -> Self {
+        Self::ProviderError {
+            provider: provider.into(),
+            base: base.into(),
+            target: target.into(),
+            source: Arc::from(source.into()),
+        }
+    }
+
+    /// Create a new ConversionError
+    pub fn conversion_error(base: impl Into<String>, target: impl Into<String>, source: MoneyError) -> Self {
+        Self::ConversionError {
+            base: base.into(),
+            target: target.into(),
+            source,
+        }
+    }
+
+    /// Create a new UnknownCurrency error
+    pub fn unknown_currency(code: impl Into<String>) -> Self {
+        Self::UnknownCurrency { code: code.into() }
+    }
+
+    /// Outcome of asking every provider in a fallback chain for a pair: `NoRateFound`
+    /// when none of them knows it, `AllProvidersFailed` when any of them failed.
+    pub fn from_provider_errors(
+        base: impl Into<String>,
+        target: impl Into<String>,
+        errors: Vec<ExchangeError>
+    ) -> Self {
+        if errors.iter().all(ExchangeError::is_no_rate_found) {
+            Self::no_rate_found(base, target)
+        } else {
+            Self::AllProvidersFailed {
+                base: base.into(),
+                target: target.into(),
+                errors,
+            }
+        }
+    }
+
+    pub fn is_no_rate_found(&self) -> bool {
+        matches!(self, Self::NoRateFound { .. })
+    }
+
+    /// Get the error category as a string
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::CurrencyMismatch { .. } => "CurrencyMismatch",
+            Self::NoRateFound { .. } => "NoRateFound",
+            Self::ExpiredRate { .. } => "ExpiredRate",
+            Self::InvalidRate { .. } => "InvalidRate",
+            Self::ProviderError { .. } => "ProviderError",
+            Self::AllProvidersFailed { .. } => "AllProvidersFailed",
+            Self::ConversionError { .. } => "ConversionError",
+            Self::UnknownCurrency { .. } => "UnknownCurrency",
+        }
+    }
+
+    /// Check if this is a recoverable error
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::CurrencyMismatch { .. } => false,
+            Self::NoRateFound { .. } => false,
+            Self::ExpiredRate { .. } => true, // A fresh rate can be fetched
+            Self::InvalidRate { .. } => false,
+            Self::ProviderError { .. } => true, // The provider may come back
+            Self::AllProvidersFailed { errors, .. } => errors.iter().any(ExchangeError::is_recoverable),
+            Self::ConversionError { .. } => false,
+            Self::UnknownCurrency { .. } => false,
+        }
+    }
+
+    /// Stable machine-readable code, see `Error::code`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::CurrencyMismatch { .. } => "exchange.currency_mismatch",
+            Self::NoRateFound { .. } => "exchange.no_rate_found",
+            Self::ExpiredRate { .. } => "exchange.expired_rate",
+            Self::InvalidRate { .. } => "exchange.invalid_rate",
+            Self::ProviderError { .. } => "exchange.provider_error",
+            Self::AllProvidersFailed { .. } => "exchange.all_providers_failed",
+            Self::ConversionError { .. } => "exchange.conversion_error",
+            Self::UnknownCurrency { .. } => "exchange.unknown_currency",
+        }
+    }
+}
+
+impl PartialEq for ExchangeError {
+    /// Provider errors compare by provider, pair and message, as their sources are opaque
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::CurrencyMismatch { expected: e1, actual: a1 }, Self::CurrencyMismatch { expected: e2, actual: a2 }) => {
+                e1 == e2 && a1 == a2
+            }
+            (Self::NoRateFound { base: b1, target: t1 }, Self::NoRateFound { base: b2, target: t2 }) => {
+                b1 == b2 && t1 == t2
+            }
+            (
+                Self::ExpiredRate { base: b1, target: t1, expired_at: x1 },
+                Self::ExpiredRate { base: b2, target: t2, expired_at: x2 },
+            ) => b1 == b2 && t1 == t2 && x1 == x2,
+            (
+                Self::InvalidRate { base: b1, target: t1, reason: r1 },
+                Self::InvalidRate { base: b2, target: t2, reason: r2 },
+            ) => b1 == b2 && t1 == t2 && r1 == r2,
+            (
+                Self::ProviderError { provider: p1, base: b1, target: t1, source: s1 },
+                Self::ProviderError { provider: p2, base: b2, target: t2, source: s2 },
+            ) => p1 == p2 && b1 == b2 && t1 == t2 && s1.to_string() == s2.to_string(),
+            (
+                Self::AllProvidersFailed { base: b1, target: t1, errors: e1 },
+                Self::AllProvidersFailed { base: b2, target: t2, errors: e2 },
+            ) => b1 == b2 && t1 == t2 && e1 == e2,
+            (
+                Self::ConversionError { base: b1, target: t1, source: s1 },
+                Self::ConversionError { base: b2, target: t2, source: s2 },
+            ) => b1 == b2 && t1 == t2 && s1 == s2,
+            (Self::UnknownCurrency { code: c1 }, Self::UnknownCurrency { code: c2 }) => c1 == c2,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for ExchangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CurrencyMismatch { expected, actual } => {
+                write!(f, "Exchange rate converts from '{}' but the amount is in '{}'", expected, actual)
+            }
+            Self::NoRateFound { base, target } => write!(f, "No exchange rate found for {}/{}", base, target),
+            Self::ExpiredRate { base, target, expired_at } => {
+                write!(f, "Exchange rate {}/{} expired at {}", base, target, expired_at.to_rfc3339())
+            }
+            Self::InvalidRate { base, target, reason } => {
+                write!(f, "Invalid exchange rate {}/{}: {}", base, target, reason)
+            }
+            Self::ProviderError { provider, base, target, source } => {
+                write!(f, "Provider '{}' failed to fetch {}/{}: {}", provider, base, target, source)
+            }
+            Self::AllProvidersFailed { base, target, errors } => {
+                write!(f, "All {} exchange rate providers failed for {}/{}", errors.len(), base, target)?;
+                for err in errors {
+                    write!(f, "; {}", err)?;
+                }
+                Ok(())
+            }
+            Self::ConversionError { base, target, source } => {
+                write!(f, "Cannot convert {} to {}: {}", base, target, source)
+            }
+            Self::UnknownCurrency { code } => write!(f, "Unknown currency code: {}", code),
+        }
+    }
+}
+
+impl error::Error for ExchangeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::ProviderError { source, .. } => Some(source.as_ref()),
+            Self::ConversionError { source, .. } => Some(source),
+            Self::AllProvidersFailed { errors, .. } => errors
+                .iter()
+                .find(|err| !err.is_no_rate_found())
+                .map(|err| err as &(dyn error::Error + 'static)),
+            _ => None,
+        }
+    }
+}
+
+
+
+/// Any error raised by this crate, so callers can use `?` across money arithmetic,
+/// currency lookups, exchange and configuration, and handle failures in one place.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    Money(MoneyError),
+    Currency(CurrencyError),
+    Exchange(ExchangeError),
+    Config(ConfigError),
+}
+
+impl Error {
+    /// Stable machine-readable code such as `exchange.no_rate_found`: the error's
+    /// domain, then its variant in snake case. Codes never change once published, so
+    /// they are safe to map to HTTP statuses or to match on in clients.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Money(err) => err.code(),
+            Self::Currency(err) => err.code(),
+            Self::Exchange(err) => err.code(),
+            Self::Config(err) => err.code(),
+        }
+    }
+
+    /// Get the error category as a string
+    pub fn category(&self) -> &'static str {
+        match self {
+            Self::Money(err) => err.category(),
+            Self::Currency(err) => err.category(),
+            Self::Exchange(err) => err.category(),
+            Self::Config(err) => err.category(),
+        }
+    }
+
+    /// Check if this is a recoverable error
+    pub fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Money(err) => err.is_recoverable(),
+            Self::Currency(err) => err.is_recoverable(),
+            Self::Exchange(err) => err.is_recoverable(),
+            Self::Config(err) => err.is_recoverable(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Money(err) => err.fmt(f),
+            Self::Currency(err) => err.fmt(f),
+            Self::Exchange(err) => err.fmt(f),
+            Self::Config(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    // Display already shows the wrapped error, so it is not repeated as the source
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Money(err) => err.source(),
+            Self::Currency(err) => err.source(),
+            Self::Exchange(err) => err.source(),
+            Self::Config(err) => err.source(),
+        }
+    }
+}
+
+impl From<MoneyError> for Error {
+    fn from(err: MoneyError) -> Self {
+        Self::Money(err)
+    }
+}
+
+impl From<CurrencyError> for Error {
+    fn from(err: CurrencyError) -> Self {
+        Self::Currency(err)
+    }
+}
+
+impl From<ExchangeError> for Error {
+    fn from(err: ExchangeError) -> Self {
+        Self::Exchange(err)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(err: ConfigError) -> Self {
+        Self::Config(err)
+    }
+}
+
+/// Result alias for APIs returning the crate-wide `Error`
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,38 +798,52 @@ mod tests {
         let err = CurrencyError::invalid_format_with_input("Expected numeric value", "$abc");
         assert_eq!(err.to_string(), "Invalid currency format: Expected numeric value (input: '$abc')");
     }
-}
 
+    #[test]
+    fn test_unified_error() {
+        use crate::core::Monetary;
+        use crate::core::currency::Currency;
+        use crate::exchange::base_exchange::CurrencyConversion;
+        use rust_decimal::Decimal;
 
+        // `?` lifts money, currency and exchange errors into `Error`
+        fn total_in_eur(conversion: &CurrencyConversion<Decimal>, code: &str) -> Result<Monetary<Decimal>> {
+            let currency = crate::core::registry::CurrencyRegistry::global()
+                .get(code)
+                .ok_or_else(|| CurrencyError::unknown_currency(code))?;
+            let amount = Monetary::new(Decimal::from(10), currency.clone())
+                .checked_add(&Monetary::new(Decimal::from(5), currency))?;
+            Ok(conversion.convert(&amount, &Currency::eur())?)
+        }
 
+        let conversion = CurrencyConversion::<Decimal>::new();
+        assert_eq!(total_in_eur(&conversion, "EUR").unwrap().amount, Decimal::from(15));
 
+        let err = total_in_eur(&conversion, "XYZ").unwrap_err();
+        assert_eq!(err, Error::Currency(CurrencyError::unknown_currency("XYZ")));
+        assert_eq!(err.code(), "currency.unknown_currency");
 
+        let err = total_in_eur(&conversion, "USD").unwrap_err();
+        assert_eq!(err.code(), "exchange.no_rate_found");
+        assert_eq!(err.category(), "NoRateFound");
+        assert!(!err.is_recoverable());
+        assert_eq!(err.to_string(), "No exchange rate found for USD/EUR");
 
-/// Specialized error type for better performance
-#[derive(Debug, PartialEq, Clone)]
-pub enum ExchangeError {
-    CurrencyMismatch,
-    NoRateFound,
-    ExpiredRate,
-    InvalidRate,
-    ProviderError,
-    ConversionError,
-}
+        let err = Error::from(MoneyError::CurrencyMismatch("USD".into(), "EUR".into()));
+        assert_eq!(err.code(), "money.currency_mismatch");
+        assert_eq!(err.to_string(), "Currency mismatch: expected 'USD', got 'EUR'");
+        assert!(Error::from(MoneyError::PrecisionLoss).is_recoverable());
+        assert_eq!(Error::from(ConfigError::unsupported_format("rates.yaml")).code(), "config.unsupported_format");
 
-impl fmt::Display for ExchangeError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            ExchangeError::CurrencyMismatch => write!(f, "Currency mismatch for exchange rate"),
-            ExchangeError::NoRateFound => write!(f, "No exchange rate found"),
-            ExchangeError::ExpiredRate => write!(f, "Exchange rate has expired"),
-            ExchangeError::InvalidRate => write!(f, "Invalid exchange rate"),
-            ExchangeError::ProviderError => write!(f, "Exchange rate provider error"),
-            ExchangeError::ConversionError => write!(f, "Type conversion error"),
-        }
+        // The cause of a wrapped error stays reachable
+        let err = Error::from(ExchangeError::conversion_error("USD", "EUR", MoneyError::Overflow("multiply".into())));
+        assert_eq!(error::Error::source(&err).unwrap().to_string(), "Arithmetic overflow in multiply");
     }
-}
-
-impl error::Error for ExchangeError {}
-
-
 
+    #[test]
+    fn test_invalid_format_at_position() {
+        let err = CurrencyError::invalid_format_at("Unexpected character 'x'", "$12x", 3);
+        assert_eq!(err.category(), "InvalidFormat");
+        assert_eq!(err.to_string(), "Invalid currency format: Unexpected character 'x' at byte 3 (input: '$12x')");
+    }
+}
@@ -0,0 +1,604 @@
+/// Async exchange rate providers and conversion service, for providers that do I/O
+use crate::core::{Monetary, Monetizable, MonetaryContext};
+use crate::core::currency::Currency;
+use crate::core::currency_id::CurrencyId;
+use crate::core::registry::CurrencyRegistry;
+use crate::errors::ExchangeError;
+use crate::exchange::base_exchange::{shortest_path, CurrencyPair, ExchangeRate, ExchangeRateProvider};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Async counterpart of `ExchangeRateProvider`. Lookups report why they failed
+/// instead of returning `None`.
+#[async_trait]
+pub trait AsyncExchangeRateProvider<T: Monetizable + Send + Sync>: Send + Sync {
+    async fn get_exchange_rate(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency
+    ) -> Result<ExchangeRate<T>, ExchangeError>;
+
+    /// Rate in effect at `as_of`. Providers without history return their current rate
+    /// when its validity period covers `as_of`.
+    async fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        let rate = self.get_exchange_rate(base_currency, target_currency).await?;
+        if rate.is_valid_at(as_of) {
+            Ok(rate)
+        } else {
+            Err(ExchangeError::no_rate_found(base_currency.code(), target_currency.code()))
+        }
+    }
+
+    /// Name reported in errors; defaults to the provider's type name
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Pairs the provider can quote, used by path search; empty if unknown
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        Vec::new()
+    }
+}
+
+/// Exposes a synchronous provider through `AsyncExchangeRateProvider`.
+///
+/// Lookups run inline on the calling task, which suits in-memory providers such as
+/// `StaticRateProvider` or `CachedExchangeRateProvider`.
+pub struct AsyncProviderAdapter<T: Monetizable> {
+    inner: Arc<dyn ExchangeRateProvider<T>>,
+}
+
+impl<T: Monetizable> AsyncProviderAdapter<T> {
+    pub fn new(inner: Arc<dyn ExchangeRateProvider<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<T: Monetizable + Send + Sync> AsyncExchangeRateProvider<T> for AsyncProviderAdapter<T> {
+    async fn get_exchange_rate(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.inner.get_exchange_rate(base_currency, target_currency)
+    }
+
+    async fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.inner.get_exchange_rate_at(base_currency, target_currency, as_of)
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        self.inner.known_pairs()
+    }
+}
+
+/// Exposes an async provider through the synchronous `ExchangeRateProvider` by
+/// blocking the calling thread on each lookup.
+///
+/// Call it from a plain thread (e.g. inside `tokio::task::spawn_blocking`), never from
+/// an async task. Providers whose futures need a runtime for timers or sockets also need
+/// that runtime entered on the calling thread (`tokio::runtime::Handle::enter`).
+pub struct BlockingProviderAdapter<T: Monetizable> {
+    inner: Arc<dyn AsyncExchangeRateProvider<T>>,
+}
+
+impl<T: Monetizable> BlockingProviderAdapter<T> {
+    pub fn new(inner: Arc<dyn AsyncExchangeRateProvider<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: Monetizable + Send + Sync> ExchangeRateProvider<T> for BlockingProviderAdapter<T> {
+    fn get_exchange_rate(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        futures::executor::block_on(self.inner.get_exchange_rate(base_currency, target_currency))
+    }
+
+    fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        futures::executor::block_on(self.inner.get_exchange_rate_at(base_currency, target_currency, as_of))
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        self.inner.known_pairs()
+    }
+}
+
+/// Async conversion service with fallback providers, mirroring `CurrencyConversion`.
+///
+/// Providers are tried in order; a provider that fails falls through to the next one.
+/// When none of them has the rate, the error is `NoRateFound` if no provider knows the
+/// pair and `AllProvidersFailed` with every provider's error otherwise. Missing rates
+/// can be derived from the reverse pair, pivot currencies or a path search, as with
+/// `CurrencyConversion`.
+pub struct AsyncCurrencyConversion<T: Monetizable> {
+    providers: Vec<Arc<dyn AsyncExchangeRateProvider<T>>>,
+    rate_cache: RwLock<HashMap<CurrencyPair, ExchangeRate<T>>>,
+    default_context: MonetaryContext,
+    registry: Option<Arc<CurrencyRegistry>>,
+    inverse_rates: bool,
+    pivots: Vec<Currency>,
+    max_hops: usize,
+}
+
+impl<T: Monetizable + Send + Sync> AsyncCurrencyConversion<T> {
+    pub fn new() -> Self {
+        Self::with_context(MonetaryContext::default())
+    }
+
+    pub fn with_context(context: MonetaryContext) -> Self {
+        Self {
+            providers: Vec::new(),
+            rate_cache: RwLock::new(HashMap::new()),
+            default_context: context,
+            registry: None,
+            inverse_rates: false,
+            pivots: Vec::new(),
+            max_hops: 0,
+        }
+    }
+
+    /// Derive a missing rate as 1 / rate of the reverse pair, including for pivot legs
+    /// and path search
+    pub fn with_inverse_rates(mut self) -> Self {
+        self.inverse_rates = true;
+        self
+    }
+
+    /// Convert through `pivot` when no direct rate exists; pivots are tried in the order added
+    pub fn with_pivot(mut self, pivot: Currency) -> Self {
+        self.pivots.push(pivot);
+        self
+    }
+
+    /// Search the pairs the providers report in `known_pairs` for the shortest chain of
+    /// at most `max_hops` rates
+    pub fn with_path_search(mut self, max_hops: usize) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// Resolve currency codes against `registry` instead of the global one
+    pub fn with_registry(mut self, registry: Arc<CurrencyRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    pub fn registry(&self) -> &CurrencyRegistry {
+        self.registry.as_deref().unwrap_or(CurrencyRegistry::global())
+    }
+
+    pub fn default_context(&self) -> &MonetaryContext {
+        &self.default_context
+    }
+
+    pub fn add_provider(&mut self, provider: Arc<dyn AsyncExchangeRateProvider<T>>) {
+        self.providers.push(provider);
+    }
+
+    /// Current rate from `base` to `target`, served from the cache while it is fresh.
+    /// Rates without a ttl are not cached.
+    pub async fn get_rate(&self, base: &Currency, target: &Currency) -> Result<ExchangeRate<T>, ExchangeError> {
+        let pair = CurrencyPair::new(base, target);
+        {
+            let cache = self.rate_cache.read().unwrap();
+            if let Some(rate) = cache.get(&pair) {
+                if !rate.is_expired() {
+                    return Ok(rate.clone());
+                }
+            }
+        }
+
+        let rate = self.find_rate(base, target, None).await?;
+        // As in `CurrencyConversion::convert`, only rates with a ttl are reused
+        if rate.get_ttl().is_some() {
+            let mut cache = self.rate_cache.write().unwrap();
+            cache.insert(pair, rate.clone());
+        }
+        Ok(rate)
+    }
+
+    /// Rate from `base` to `target` in effect at `as_of`. Historical rates bypass the cache.
+    pub async fn get_rate_at(
+        &self,
+        base: &Currency,
+        target: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.find_rate(base, target, Some(as_of)).await
+    }
+
+    /// Direct rate, then through each pivot, then along the shortest known path; the
+    /// async counterpart of `CurrencyConversion::find_rate`
+    async fn find_rate(
+        &self,
+        base: &Currency,
+        target: &Currency,
+        as_of: Option<DateTime<Utc>>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        let direct = self.find_leg(base, target, as_of).await;
+        if direct.is_ok() {
+            return direct;
+        }
+
+        for pivot in &self.pivots {
+            if pivot.same_currency(base) || pivot.same_currency(target) {
+                continue;
+            }
+            if let Ok(first) = self.find_leg(base, pivot, as_of).await {
+                if let Ok(second) = self.find_leg(pivot, target, as_of).await {
+                    return first.combine(&second);
+                }
+            }
+        }
+
+        if self.max_hops > 1 {
+            let pairs: Vec<CurrencyPair> = self.providers.iter().flat_map(|provider| provider.known_pairs()).collect();
+            if let Some(path) = shortest_path(&pairs, self.inverse_rates, self.max_hops, base, target, self.registry()) {
+                let mut legs = Vec::new();
+                for step in path.windows(2) {
+                    match self.find_leg(&step[0], &step[1], as_of).await {
+                        Ok(leg) => legs.push(leg),
+                        Err(_) => break,
+                    }
+                }
+                if legs.len() == path.len() - 1 {
+                    if let Some((first, rest)) = legs.split_first() {
+                        return rest.iter().try_fold(first.clone(), |rate, leg| rate.combine(leg));
+                    }
+                }
+            }
+        }
+
+        // No cross rate either: report why the direct lookup failed
+        direct
+    }
+
+    /// Direct rate from the first provider quoting the pair, or the inverse of the reverse pair
+    async fn find_leg(
+        &self,
+        base: &Currency,
+        target: &Currency,
+        as_of: Option<DateTime<Utc>>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        match self.query_providers(base, target, as_of).await {
+            Err(direct) if self.inverse_rates => self
+                .query_providers(target, base, as_of)
+                .await
+                .and_then(|rate| rate.inverse())
+                .map_err(|reverse| {
+                    // A failing provider matters more than a pair nobody quotes
+                    if direct.is_no_rate_found() && !reverse.is_no_rate_found() { reverse } else { direct }
+                }),
+            result => result,
+        }
+    }
+
+    /// Rate from the first provider that has one, labelled with its name unless the
+    /// provider set one; when none has, every provider's error is collected
+    async fn query_providers(
+        &self,
+        base: &Currency,
+        target: &Currency,
+        as_of: Option<DateTime<Utc>>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        let mut errors = Vec::new();
+        for provider in &self.providers {
+            let lookup = match as_of {
+                Some(as_of) => provider.get_exchange_rate_at(base, target, as_of).await,
+                None => provider.get_exchange_rate(base, target).await,
+            };
+            match lookup {
+                Ok(rate) if rate.get_provider().is_some() => return Ok(rate),
+                Ok(rate) => return Ok(rate.with_provider(provider.name())),
+                Err(e) => errors.push(e),
+            }
+        }
+        Err(ExchangeError::from_provider_errors(base.code(), target.code(), errors))
+    }
+
+    pub async fn convert(
+        &self,
+        amount: &Monetary<T>,
+        target_currency: &Currency
+    ) -> Result<Monetary<T>, ExchangeError> {
+        if amount.currency.same_currency(target_currency) {
+            return Ok(amount.clone());
+        }
+
+        self.get_rate(&amount.currency, target_currency).await?.apply(amount)
+    }
+
+    /// Convert with the rate in effect at `as_of`
+    pub async fn convert_at(
+        &self,
+        amount: &Monetary<T>,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<Monetary<T>, ExchangeError> {
+        if amount.currency.same_currency(target_currency) {
+            return Ok(amount.clone());
+        }
+
+        self.get_rate_at(&amount.currency, target_currency, as_of).await?.apply(amount)
+    }
+
+    /// Convert to the currency registered under `target_code`
+    pub async fn convert_to_code(
+        &self,
+        amount: &Monetary<T>,
+        target_code: &str
+    ) -> Result<Monetary<T>, ExchangeError> {
+        let target_currency = self.registry().get(target_code).ok_or_else(|| ExchangeError::unknown_currency(target_code))?;
+        self.convert(amount, &target_currency).await
+    }
+
+    /// Cross-type conversion with rounding
+    pub async fn convert_to<U: Monetizable + 'static>(
+        &self,
+        amount: &Monetary<T>,
+        target_currency: &Currency
+    ) -> Result<Monetary<U>, ExchangeError> {
+        if amount.currency.same_currency(target_currency) {
+            let converted_amount = amount.amount.try_to_decimal()
+                .and_then(U::try_from_decimal)
+                .map_err(|e| ExchangeError::conversion_error(amount.currency.code(), target_currency.code(), e))?;
+            return Ok(Monetary::new(converted_amount, target_currency.clone()));
+        }
+
+        self.get_rate(&amount.currency, target_currency).await?.apply_convert::<U>(amount)
+    }
+
+    /// Batch conversion; rates for the distinct source currencies are fetched concurrently
+    pub async fn convert_batch(
+        &self,
+        amounts: &[Monetary<T>],
+        target_currency: &Currency
+    ) -> Vec<Result<Monetary<T>, ExchangeError>> {
+        let mut sources: HashMap<CurrencyId, &Currency> = HashMap::new();
+        for amount in amounts {
+            if !amount.currency.same_currency(target_currency) {
+                sources.entry(amount.currency.id()).or_insert(&amount.currency);
+            }
+        }
+
+        let lookups = sources.into_iter().map(|(id, currency)| async move {
+            (id, self.get_rate(currency, target_currency).await)
+        });
+        let rates: HashMap<CurrencyId, Result<ExchangeRate<T>, ExchangeError>> =
+            futures::future::join_all(lookups).await.into_iter().collect();
+
+        amounts
+            .iter()
+            .map(|amount| match rates.get(&amount.currency.id()) {
+                None => Ok(amount.clone()),
+                Some(Ok(rate)) => rate.apply(amount),
+                Some(Err(e)) => Err(e.clone()),
+            })
+            .collect()
+    }
+}
+
+impl<T: Monetizable + Send + Sync> Default for AsyncCurrencyConversion<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::static_exchange::StaticRateProvider;
+    use rust_decimal::Decimal;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Provider that awaits a timer before answering, like a remote feed would
+    struct RemoteProvider {
+        calls: AtomicUsize,
+        rates: HashMap<CurrencyPair, Decimal>,
+        fail: bool,
+    }
+
+    impl RemoteProvider {
+        fn new(rates: &[(Currency, Currency, Decimal)]) -> Self {
+            Self {
+                calls: AtomicUsize::new(0),
+                rates: rates
+                    .iter()
+                    .map(|(base, target, rate)| (CurrencyPair::new(base, target), *rate))
+                    .collect(),
+                fail: false,
+            }
+        }
+
+        fn failing() -> Self {
+            Self { fail: true, ..Self::new(&[]) }
+        }
+    }
+
+    #[async_trait]
+    impl AsyncExchangeRateProvider<Decimal> for RemoteProvider {
+        async fn get_exchange_rate(
+            &self,
+            base_currency: &Currency,
+            target_currency: &Currency
+        ) -> Result<ExchangeRate<Decimal>, ExchangeError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            if self.fail {
+                return Err(ExchangeError::provider_error(
+                    "remote",
+                    base_currency.code(),
+                    target_currency.code(),
+                    "HTTP 503 Service Unavailable"
+                ));
+            }
+            let rate = self
+                .rates
+                .get(&CurrencyPair::new(base_currency, target_currency))
+                .ok_or_else(|| ExchangeError::no_rate_found(base_currency.code(), target_currency.code()))?;
+            Ok(ExchangeRate::new(base_currency.clone(), target_currency.clone(), *rate).with_ttl(Duration::from_secs(60)))
+        }
+
+        fn name(&self) -> &str {
+            "remote"
+        }
+
+        fn known_pairs(&self) -> Vec<CurrencyPair> {
+            self.rates.keys().cloned().collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_conversion_with_fallback() {
+        let (usd, eur, gbp) = (Currency::usd(), Currency::eur(), Currency::gbp());
+        let remote = Arc::new(RemoteProvider::new(&[(usd.clone(), eur.clone(), Decimal::new(85, 2))]));
+
+        let mut conversion = AsyncCurrencyConversion::<Decimal>::new();
+        conversion.add_provider(Arc::new(RemoteProvider::failing()));
+        conversion.add_provider(remote.clone());
+
+        let dollars = Monetary::new(Decimal::from(100), usd.clone());
+        assert_eq!(conversion.convert(&dollars, &eur).await.unwrap().amount, Decimal::from(85));
+        assert_eq!(conversion.convert_to_code(&dollars, "EUR").await.unwrap().amount, Decimal::from(85));
+        // The second conversion was served from the cache
+        assert_eq!(remote.calls.load(Ordering::SeqCst), 1);
+
+        let converted: Monetary<f64> = conversion.convert_to(&dollars, &eur).await.unwrap();
+        assert_eq!(converted.amount, 85.0);
+
+        // Every provider missed and one failed: each provider's error is reported
+        let err = conversion.convert(&dollars, &gbp).await.unwrap_err();
+        assert_eq!(err, ExchangeError::AllProvidersFailed {
+            base: "USD".into(),
+            target: "GBP".into(),
+            errors: vec![
+                ExchangeError::provider_error("remote", "USD", "GBP", "HTTP 503 Service Unavailable"),
+                ExchangeError::no_rate_found("USD", "GBP"),
+            ],
+        });
+    }
+
+    #[tokio::test]
+    async fn test_async_cross_rates() {
+        let (usd, eur, gbp, jpy) = (Currency::usd(), Currency::eur(), Currency::gbp(), Currency::jpy());
+        let remote = || Arc::new(RemoteProvider::new(&[
+            (eur.clone(), usd.clone(), Decimal::new(108, 2)),
+            (eur.clone(), gbp.clone(), Decimal::new(85, 2)),
+            (gbp.clone(), jpy.clone(), Decimal::from(190)),
+        ]));
+        let dollars = Monetary::new(Decimal::from(108), usd.clone());
+
+        // Direct rates only by default
+        let mut direct = AsyncCurrencyConversion::<Decimal>::new();
+        direct.add_provider(remote());
+        assert_eq!(direct.convert(&dollars, &eur).await.err(), Some(ExchangeError::no_rate_found("USD", "EUR")));
+
+        let mut inverse = AsyncCurrencyConversion::<Decimal>::new().with_inverse_rates();
+        inverse.add_provider(remote());
+        assert_eq!(inverse.convert(&dollars, &eur).await.unwrap().amount, Decimal::from(100));
+        let rate = inverse.get_rate(&usd, &eur).await.unwrap();
+        assert_eq!(rate.get_chain(), &[CurrencyPair::new(&eur, &usd)]);
+        assert_eq!(rate.get_provider(), Some("remote"));
+
+        let mut pivot = AsyncCurrencyConversion::<Decimal>::new().with_inverse_rates().with_pivot(eur.clone());
+        pivot.add_provider(remote());
+        assert_eq!(pivot.convert(&dollars, &gbp).await.unwrap().amount, Decimal::new(8500, 2));
+
+        let mut path = AsyncCurrencyConversion::<Decimal>::new().with_inverse_rates().with_path_search(3);
+        path.add_provider(remote());
+        let rate = path.get_rate(&usd, &jpy).await.unwrap();
+        assert_eq!(rate.get_chain(), &[
+            CurrencyPair::new(&eur, &usd),
+            CurrencyPair::new(&eur, &gbp),
+            CurrencyPair::new(&gbp, &jpy),
+        ]);
+        assert_eq!(path.convert(&dollars, &jpy).await.unwrap().amount, Decimal::from(16150));
+    }
+
+    #[tokio::test]
+    async fn test_async_convert_batch() {
+        let (usd, eur, gbp) = (Currency::usd(), Currency::eur(), Currency::gbp());
+        let remote = Arc::new(RemoteProvider::new(&[
+            (usd.clone(), eur.clone(), Decimal::new(85, 2)),
+            (gbp.clone(), eur.clone(), Decimal::new(115, 2)),
+        ]));
+        let mut conversion = AsyncCurrencyConversion::<Decimal>::new();
+        conversion.add_provider(remote.clone());
+
+        let amounts = vec![
+            Monetary::new(Decimal::from(100), usd.clone()),
+            Monetary::new(Decimal::from(10), eur.clone()),
+            Monetary::new(Decimal::from(200), usd.clone()),
+            Monetary::new(Decimal::from(100), gbp.clone()),
+            Monetary::new(Decimal::from(1), Currency::jpy()),
+        ];
+        let results = conversion.convert_batch(&amounts, &eur).await;
+        let converted: Vec<_> = results.iter().map(|r| r.as_ref().ok().map(|m| m.amount)).collect();
+        assert_eq!(converted, vec![
+            Some(Decimal::from(85)),
+            Some(Decimal::from(10)),
+            Some(Decimal::from(170)),
+            Some(Decimal::from(115)),
+            None,
+        ]);
+        assert_eq!(results[4], Err(ExchangeError::no_rate_found("JPY", "EUR")));
+        // One lookup per distinct source currency
+        assert_eq!(remote.calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_adapters() {
+        let (usd, eur) = (Currency::usd(), Currency::eur());
+        let mut fixed = StaticRateProvider::new();
+        fixed.add_rate(&usd, &eur, Decimal::new(85, 2));
+
+        let mut conversion = AsyncCurrencyConversion::<Decimal>::new();
+        conversion.add_provider(Arc::new(AsyncProviderAdapter::new(Arc::new(fixed))));
+        let dollars = Monetary::new(Decimal::from(100), usd.clone());
+        assert_eq!(conversion.convert(&dollars, &eur).await.unwrap().amount, Decimal::from(85));
+        assert_eq!(conversion.get_rate(&eur, &usd).await.err(), Some(ExchangeError::no_rate_found("EUR", "USD")));
+
+        // An async provider used from synchronous code on a blocking thread
+        let remote: Arc<dyn AsyncExchangeRateProvider<Decimal>> =
+            Arc::new(RemoteProvider::new(&[(usd.clone(), eur.clone(), Decimal::new(85, 2))]));
+        let handle = tokio::runtime::Handle::current();
+        let rate = tokio::task::spawn_blocking(move || {
+            let _guard = handle.enter();
+            let blocking = BlockingProviderAdapter::new(remote);
+            blocking.get_exchange_rate(&Currency::usd(), &Currency::eur())
+        })
+        .await
+        .unwrap();
+        assert_eq!(rate.map(|r| *r.get_factor()), Ok(Decimal::new(85, 2)));
+    }
+}
@@ -1,37 +1,110 @@
-use crate::core::{Monetary, Monetizable, MonetaryContext};
+use crate::core::{Monetary, Monetizable, MonetaryContext, MoneyError};
 use crate::core::currency::Currency;
+use crate::core::currency_id::CurrencyId;
 use crate::core::currency_unit::CurrencyUnit;
-use crate::constants::RoundingMode;
+use crate::core::registry::CurrencyRegistry;
 use crate::errors::ExchangeError;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
 use rust_decimal::Decimal;
+use chrono::{DateTime, Utc};
 
-/// Fast hash-based key for currency pairs using numeric codes
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// Fast hash-based key for currency pairs using currency ids
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct CurrencyPair {
-    base_code: i32,
-    target_code: i32,
+    base: CurrencyId,
+    target: CurrencyId,
 }
 
 impl CurrencyPair {
     pub fn new(base: &Currency, target: &Currency) -> Self {
         Self {
-            base_code: base.numeric_code(),
-            target_code: target.numeric_code(),
+            base: base.id(),
+            target: target.id(),
         }
     }
     
     pub fn from_units(base: &CurrencyUnit, target: &CurrencyUnit) -> Self {
         Self {
-            base_code: base.get_numeric_code(),
-            target_code: target.get_numeric_code(),
+            base: CurrencyId::new(base.get_code()),
+            target: CurrencyId::new(target.get_code()),
         }
     }
+
+    pub fn base(&self) -> CurrencyId {
+        self.base
+    }
+
+    pub fn target(&self) -> CurrencyId {
+        self.target
+    }
+
+    /// The same pair quoted the other way round
+    pub fn inverse(&self) -> Self {
+        Self {
+            base: self.target,
+            target: self.base,
+        }
+    }
+}
+
+/// Which side of a quoted rate a conversion uses.
+///
+/// Quotes follow the market convention for the pair: the bid is what the desk pays
+/// in target currency for one unit of base, the ask what it charges for one. `Mid`
+/// is the rate's factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RateSide {
+    Bid,
+    Ask,
+    #[default]
+    Mid,
+}
+
+/// Spread added on each side of the provider's mid rate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    /// Percent of the mid, e.g. 0.5 for a bid 0.5% below and an ask 0.5% above it
+    Percent(Decimal),
+    /// Basis points of the mid, e.g. 25 for 0.25%
+    BasisPoints(Decimal),
+}
+
+impl Markup {
+    /// Markup as a fraction of the mid
+    pub fn fraction(&self) -> Decimal {
+        match self {
+            Markup::Percent(percent) => percent / Decimal::ONE_HUNDRED,
+            Markup::BasisPoints(points) => points / Decimal::from(10_000),
+        }
+    }
+}
+
+/// Result of converting at one side of a rate
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpreadConversion<T: Monetizable + 'static> {
+    /// The amount in the target currency at the chosen side
+    pub converted: Monetary<T>,
+    /// Difference to converting at the mid, in the target currency; zero at the mid
+    pub spread_revenue: Monetary<T>,
+    pub side: RateSide,
 }
 
-/// High-performance exchange rate with monetizable factor
+/// High-performance exchange rate with monetizable factor.
+///
+/// `ttl` bounds how long a fetched rate may be reused, while `valid_from`/`valid_to`
+/// record the period the rate was in effect (e.g. the ECB reference rate of one day).
+/// Cross rates built with `inverse` and `combine` keep the published pairs they were
+/// derived from in `get_chain`.
+///
+/// The factor is the mid rate. A rate may also carry bid and ask quotes; without them
+/// every side is the mid.
+///
+/// Applying a rate multiplies exactly and rounds once, to the context's `max_scale`
+/// with its rounding mode, whatever the numeric type. `with_currency_rounding` rounds
+/// to the target currency's minor unit instead, so 10.00 USD at 0.92154 is 9.22 EUR as
+/// a `Decimal`, `f64`, `BigDecimal` or `MinorUnits`.
 #[derive(Debug, Clone)]
 pub struct ExchangeRate<T: Monetizable> {
     base_currency: Currency,
@@ -39,7 +112,13 @@ pub struct ExchangeRate<T: Monetizable> {
     factor: T,
     timestamp: Instant,
     ttl: Option<Duration>,
+    valid_from: Option<DateTime<Utc>>,
+    valid_to: Option<DateTime<Utc>>,
+    chain: Vec<CurrencyPair>,
     context: MonetaryContext,
+    quotes: Option<(T, T)>,
+    provider: Option<String>,
+    currency_rounding: bool,
 }
 
 impl<T: Monetizable> ExchangeRate<T> {
@@ -54,7 +133,13 @@ impl<T: Monetizable> ExchangeRate<T> {
             factor,
             timestamp: Instant::now(),
             ttl: None,
+            valid_from: None,
+            valid_to: None,
+            chain: Vec::new(),
             context: MonetaryContext::default(),
+            quotes: None,
+            provider: None,
+            currency_rounding: false,
         }
     }
     
@@ -62,12 +147,63 @@ impl<T: Monetizable> ExchangeRate<T> {
         self.ttl = Some(ttl);
         self
     }
+
+    /// First instant the rate is in effect
+    pub fn with_valid_from(mut self, valid_from: DateTime<Utc>) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    /// Instant the rate stops being in effect (exclusive)
+    pub fn with_valid_to(mut self, valid_to: DateTime<Utc>) -> Self {
+        self.valid_to = Some(valid_to);
+        self
+    }
     
     pub fn with_context(mut self, context: MonetaryContext) -> Self {
         self.context = context;
         self
     }
 
+    /// Round converted amounts to the target currency's minor unit instead of the
+    /// context's `max_scale`
+    pub fn with_currency_rounding(mut self) -> Self {
+        self.currency_rounding = true;
+        self
+    }
+
+    /// Name of the provider that published the rate
+    pub fn with_provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
+    /// Bid and ask quotes around the factor, which stays the mid
+    pub fn with_quotes(mut self, bid: T, ask: T) -> Self {
+        self.quotes = Some((bid, ask));
+        self
+    }
+
+    /// Replace the quotes with `markup` on each side of the mid
+    pub fn with_markup(mut self, markup: Markup) -> Result<Self, ExchangeError> {
+        let fraction = markup.fraction();
+        if fraction < Decimal::ZERO || fraction >= Decimal::ONE {
+            return Err(ExchangeError::invalid_rate(
+                self.base_currency.code(),
+                self.target_currency.code(),
+                format!("markup of {} must be at least 0% and below 100%", fraction * Decimal::ONE_HUNDRED)
+            ));
+        }
+        let mid = self.factor.try_to_decimal().map_err(|e| self.conversion_error(e))?;
+        let side = |spread: Decimal| {
+            mid.checked_mul(spread)
+                .ok_or_else(|| self.conversion_error(MoneyError::Overflow(format!("{} * {}", mid, spread))))
+                .and_then(|value| T::try_from_decimal(value).map_err(|e| self.conversion_error(e)))
+        };
+        self.quotes = Some((side(Decimal::ONE - fraction)?, side(Decimal::ONE + fraction)?));
+        Ok(self)
+    }
+
     pub fn get_base_currency(&self) -> &Currency {
         &self.base_currency
     }
@@ -84,10 +220,200 @@ impl<T: Monetizable> ExchangeRate<T> {
     pub fn get_factor(&self) -> &T {
         &self.factor
     }
+
+    /// Bid quote, or the mid when the rate has no quotes
+    pub fn get_bid(&self) -> &T {
+        self.quotes.as_ref().map_or(&self.factor, |(bid, _)| bid)
+    }
+
+    /// Ask quote, or the mid when the rate has no quotes
+    pub fn get_ask(&self) -> &T {
+        self.quotes.as_ref().map_or(&self.factor, |(_, ask)| ask)
+    }
+
+    pub fn get_mid(&self) -> &T {
+        &self.factor
+    }
+
+    pub fn get_side(&self, side: RateSide) -> &T {
+        match side {
+            RateSide::Bid => self.get_bid(),
+            RateSide::Ask => self.get_ask(),
+            RateSide::Mid => self.get_mid(),
+        }
+    }
+
+    pub fn has_quotes(&self) -> bool {
+        self.quotes.is_some()
+    }
+
+    /// Provider the rate came from; derived rates list their providers joined with "+"
+    pub fn get_provider(&self) -> Option<&str> {
+        self.provider.as_deref()
+    }
     
     pub fn get_context(&self) -> &MonetaryContext {
         &self.context
     }
+
+    /// Scale converted amounts are rounded to: the target currency's minor unit with
+    /// `with_currency_rounding`, otherwise the context's `max_scale`
+    pub fn get_rounding_scale(&self) -> i32 {
+        if self.currency_rounding {
+            self.target_currency.precision()
+        } else {
+            self.context.max_scale()
+        }
+    }
+
+    pub fn get_valid_from(&self) -> Option<DateTime<Utc>> {
+        self.valid_from
+    }
+
+    pub fn get_valid_to(&self) -> Option<DateTime<Utc>> {
+        self.valid_to
+    }
+
+    /// Published pairs this rate was derived from, in conversion order; empty for a
+    /// rate taken directly from a provider. A pair whose base is not the currency being
+    /// converted at that step was inverted.
+    pub fn get_chain(&self) -> &[CurrencyPair] {
+        &self.chain
+    }
+
+    pub fn is_derived(&self) -> bool {
+        !self.chain.is_empty()
+    }
+
+    /// Rate for the opposite direction (1 / factor). The inverse bid is 1 / ask and
+    /// the inverse ask 1 / bid. It keeps this rate's fetch time and ttl.
+    pub fn inverse(&self) -> Result<ExchangeRate<T>, ExchangeError> {
+        let one = T::try_from_decimal(Decimal::ONE).map_err(|e| self.conversion_error(e))?;
+        let invert = |value: T| one.checked_div(value).map_err(|e| match e {
+            MoneyError::DivisionByZero => ExchangeError::invalid_rate(
+                self.base_currency.code(),
+                self.target_currency.code(),
+                "a zero rate has no inverse"
+            ),
+            e => self.conversion_error(e),
+        });
+        let factor = invert(self.factor)?;
+        let quotes = match self.quotes {
+            Some((bid, ask)) => Some((invert(ask)?, invert(bid)?)),
+            None => None,
+        };
+        Ok(ExchangeRate {
+            base_currency: self.target_currency.clone(),
+            target_currency: self.base_currency.clone(),
+            factor,
+            timestamp: self.timestamp,
+            ttl: self.ttl,
+            valid_from: self.valid_from,
+            valid_to: self.valid_to,
+            chain: self.source_pairs(),
+            context: self.context.clone(),
+            quotes,
+            provider: self.provider.clone(),
+            currency_rounding: self.currency_rounding,
+        })
+    }
+
+    /// Chain this rate with `next`, whose base must be this rate's target. The result
+    /// is valid where both are, counts as fetched when the older of the two was, and
+    /// expires with the first of them.
+    pub fn combine(&self, next: &ExchangeRate<T>) -> Result<ExchangeRate<T>, ExchangeError> {
+        if !self.target_currency.same_currency(&next.base_currency) {
+            return Err(ExchangeError::currency_mismatch(self.target_currency.code(), next.base_currency.code()));
+        }
+        let multiply = |a: T, b: T| a.checked_mul(b).map_err(|e| ExchangeError::conversion_error(
+            self.base_currency.code(),
+            next.target_currency.code(),
+            e
+        ));
+        let factor = multiply(self.factor, next.factor)?;
+        let quotes = if self.has_quotes() || next.has_quotes() {
+            Some((multiply(*self.get_bid(), *next.get_bid())?, multiply(*self.get_ask(), *next.get_ask())?))
+        } else {
+            None
+        };
+        let mut chain = self.source_pairs();
+        chain.extend(next.source_pairs());
+        let timestamp = self.timestamp.min(next.timestamp);
+        Ok(ExchangeRate {
+            base_currency: self.base_currency.clone(),
+            target_currency: next.target_currency.clone(),
+            factor,
+            timestamp,
+            ttl: min_some(self.deadline(), next.deadline()).map(|deadline| deadline.saturating_duration_since(timestamp)),
+            valid_from: self.valid_from.max(next.valid_from),
+            valid_to: min_some(self.valid_to, next.valid_to),
+            chain,
+            context: self.context.clone(),
+            quotes,
+            provider: match (&self.provider, &next.provider) {
+                (Some(first), Some(second)) if first != second => Some(format!("{}+{}", first, second)),
+                (first, second) => first.clone().or_else(|| second.clone()),
+            },
+            currency_rounding: self.currency_rounding,
+        })
+    }
+
+    fn source_pairs(&self) -> Vec<CurrencyPair> {
+        if self.chain.is_empty() {
+            vec![CurrencyPair::new(&self.base_currency, &self.target_currency)]
+        } else {
+            self.chain.clone()
+        }
+    }
+
+    /// Instant the ttl runs out; a ttl too large to add is treated as none
+    fn deadline(&self) -> Option<Instant> {
+        self.ttl.and_then(|ttl| self.timestamp.checked_add(ttl))
+    }
+
+    /// Wall-clock time at which the rate was created or fetched
+    pub fn fetched_at(&self) -> DateTime<Utc> {
+        chrono::Duration::from_std(self.timestamp.elapsed())
+            .ok()
+            .and_then(|elapsed| Utc::now().checked_sub_signed(elapsed))
+            .unwrap_or_else(Utc::now)
+    }
+
+    /// Wall-clock time at which the ttl runs out, if the rate has one
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        let ttl = self.ttl?;
+        let elapsed = self.timestamp.elapsed();
+        let now = Utc::now();
+        if ttl >= elapsed {
+            chrono::Duration::from_std(ttl - elapsed).ok().and_then(|left| now.checked_add_signed(left))
+        } else {
+            chrono::Duration::from_std(elapsed - ttl).ok().and_then(|overdue| now.checked_sub_signed(overdue))
+        }
+    }
+
+    /// Error to report when converting `amount` with this rate is not possible
+    fn check_usable(&self, amount: &Monetary<T>) -> Result<(), ExchangeError> {
+        if !amount.currency.same_currency(&self.base_currency) {
+            return Err(ExchangeError::currency_mismatch(self.base_currency.code(), amount.currency.code()));
+        }
+        if self.is_expired() {
+            return Err(ExchangeError::expired_rate(
+                self.base_currency.code(),
+                self.target_currency.code(),
+                self.expires_at().unwrap_or_else(Utc::now)
+            ));
+        }
+        Ok(())
+    }
+
+    fn conversion_error(&self, source: MoneyError) -> ExchangeError {
+        ExchangeError::conversion_error(self.base_currency.code(), self.target_currency.code(), source)
+    }
+
+    /// Check whether `as_of` falls within the validity period; open ends are unbounded
+    pub fn is_valid_at(&self, as_of: DateTime<Utc>) -> bool {
+        self.valid_from.is_none_or(|from| from <= as_of) && self.valid_to.is_none_or(|to| as_of < to)
+    }
     
     pub fn is_expired(&self) -> bool {
         if let Some(ttl) = self.ttl {
@@ -99,84 +425,232 @@ impl<T: Monetizable> ExchangeRate<T> {
 
     /// Fast application with same numeric type
     pub fn apply(&self, amount: &Monetary<T>) -> Result<Monetary<T>, ExchangeError> {
-        if amount.currency != self.base_currency {
-            return Err(ExchangeError::CurrencyMismatch);
-        }
-        
-        if self.is_expired() {
-            return Err(ExchangeError::ExpiredRate);
-        }
+        self.check_usable(amount)?;
 
         // Direct multiplication using Monetizable trait
-        let converted_amount = amount.amount * self.factor;
-        
+        let converted_amount = amount.amount.checked_mul(self.factor)
+            .map_err(|e| self.conversion_error(e))?;
+        let converted_amount = self.round_converted(converted_amount)?;
+
         Ok(Monetary::new(converted_amount, self.target_currency.clone()))
     }
     
 /// Cross-type conversion with rounding
 pub fn apply_convert<U: Monetizable>(&self, amount: &Monetary<T>) -> Result<Monetary<U>, ExchangeError> {
-    if amount.currency != self.base_currency {
-        return Err(ExchangeError::CurrencyMismatch);
-    }
-
-    if self.is_expired() {
-        return Err(ExchangeError::ExpiredRate);
-    }
+    self.check_usable(amount)?;
 
     // Convert to common decimal representation for precise calculation
     let amount_decimal = amount.amount.try_to_decimal()
-        .map_err(|_| ExchangeError::ConversionError)?;
+        .map_err(|e| self.conversion_error(e))?;
     let factor_decimal = self.factor.try_to_decimal()
-        .map_err(|_|ExchangeError::ConversionError)?;
+        .map_err(|e| self.conversion_error(e))?;
 
-    let result_decimal = amount_decimal * factor_decimal;
+    let result_decimal = Monetizable::checked_mul(amount_decimal, factor_decimal)
+        .map_err(|e| self.conversion_error(e))?;
 
-    // Apply rounding based on context
-    let rounded_decimal = self.apply_rounding(result_decimal);
-
-    // Convert to target type
-    let converted_amount = U::try_from_decimal(rounded_decimal).map_err(|_|ExchangeError::ConversionError)?;
+    // Round once in decimal, then bring the target type to the same scale
+    let rounded_decimal = self.round_converted(result_decimal)?;
+    let converted_amount = U::try_from_decimal(rounded_decimal).map_err(|e| self.conversion_error(e))?;
+    let converted_amount = self.round_converted(converted_amount)?;
 
     Ok(Monetary::new(converted_amount, self.target_currency.clone()))
 }
 
-    
-    fn apply_rounding(&self, value: Decimal) -> Decimal {
-        match self.context.rounding_mode() {
-            RoundingMode::Up => value.ceil(),
-            RoundingMode::Down => value.floor(),
-            RoundingMode::HalfUp => value.round_dp_with_strategy(
-                self.context.max_scale() as u32, 
-                rust_decimal::RoundingStrategy::MidpointAwayFromZero
-            ),
-            RoundingMode::HalfDown => value.round_dp_with_strategy(
-                self.context.max_scale() as u32,
-                rust_decimal::RoundingStrategy::MidpointTowardZero  
-            ),
-            RoundingMode::HalfEven => value.round_dp_with_strategy(
-                self.context.max_scale() as u32,
-                rust_decimal::RoundingStrategy::MidpointNearestEven
-            ),
-            RoundingMode::Unnecessary => value, // No rounding,
-            _ => value
+
+    /// Convert at `side`, reporting the spread earned against the mid
+    pub fn apply_side(&self, amount: &Monetary<T>, side: RateSide) -> Result<SpreadConversion<T>, ExchangeError> {
+        self.check_usable(amount)?;
+        self.check_quotes()?;
+
+        let convert = |factor: T| {
+            amount.amount.checked_mul(factor)
+                .map_err(|e| self.conversion_error(e))
+                .and_then(|value| self.round_converted(value))
+        };
+        let at_mid = convert(self.factor)?;
+        let at_side = convert(*self.get_side(side))?;
+        let revenue = match side {
+            RateSide::Bid => at_mid.checked_sub(at_side),
+            RateSide::Ask => at_side.checked_sub(at_mid),
+            RateSide::Mid => Ok(T::zero()),
+        }
+        .map_err(|e| self.conversion_error(e))?;
+
+        Ok(SpreadConversion {
+            converted: Monetary::new(at_side, self.target_currency.clone()),
+            spread_revenue: Monetary::new(revenue, self.target_currency.clone()),
+            side,
+        })
+    }
+
+    /// Cross-type conversion at `side`; both the converted amount and the mid it is
+    /// compared against are rounded with the rate's context
+    pub fn apply_convert_side<U: Monetizable>(
+        &self,
+        amount: &Monetary<T>,
+        side: RateSide
+    ) -> Result<SpreadConversion<U>, ExchangeError> {
+        self.check_usable(amount)?;
+        self.check_quotes()?;
+
+        let decimal = |value: &T| value.try_to_decimal().map_err(|e| self.conversion_error(e));
+        let amount_decimal = decimal(&amount.amount)?;
+        let convert = |factor: Decimal| {
+            Monetizable::checked_mul(amount_decimal, factor)
+                .map_err(|e| self.conversion_error(e))
+                .and_then(|value| self.round_converted(value))
+        };
+        let at_mid = convert(decimal(&self.factor)?)?;
+        let at_side = convert(decimal(self.get_side(side))?)?;
+        let revenue = match side {
+            RateSide::Bid => at_mid - at_side,
+            RateSide::Ask => at_side - at_mid,
+            RateSide::Mid => Decimal::ZERO,
+        };
+
+        let to_target = |value: Decimal| {
+            U::try_from_decimal(value)
+                .map_err(|e| self.conversion_error(e))
+                .and_then(|value| self.round_converted(value))
+                .map(|value| Monetary::new(value, self.target_currency.clone()))
+        };
+        Ok(SpreadConversion {
+            converted: to_target(at_side)?,
+            spread_revenue: to_target(revenue)?,
+            side,
+        })
+    }
+
+    fn check_quotes(&self) -> Result<(), ExchangeError> {
+        match self.quotes {
+            Some((bid, ask)) if bid > self.factor || ask < self.factor => Err(ExchangeError::invalid_rate(
+                self.base_currency.code(),
+                self.target_currency.code(),
+                format!("bid {} and ask {} must lie either side of the mid {}", bid, ask, self.factor)
+            )),
+            _ => Ok(()),
         }
     }
+
+    /// Round a converted amount to `get_rounding_scale` with the context's rounding mode
+    fn round_converted<V: Monetizable>(&self, value: V) -> Result<V, ExchangeError> {
+        value
+            .round_to_scale(self.get_rounding_scale(), self.context.rounding_mode())
+            .map_err(|e| self.conversion_error(e))
+    }
+}
+
+
+// Smallest of two optional bounds, where None means unbounded
+fn min_some<V: Ord>(a: Option<V>, b: Option<V>) -> Option<V> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Shortest chain of at most `max_hops` of `pairs` from `base` to `target`, walking
+/// pairs backwards too when `inverse_rates` is set; intermediate currencies are resolved
+/// through `registry`. Shared by the sync and async conversion services.
+pub(crate) fn shortest_path(
+    pairs: &[CurrencyPair],
+    inverse_rates: bool,
+    max_hops: usize,
+    base: &Currency,
+    target: &Currency,
+    registry: &CurrencyRegistry
+) -> Option<Vec<Currency>> {
+    let mut edges: HashMap<CurrencyId, Vec<CurrencyId>> = HashMap::new();
+    for pair in pairs {
+        edges.entry(pair.base()).or_default().push(pair.target());
+        if inverse_rates {
+            edges.entry(pair.target()).or_default().push(pair.base());
+        }
+    }
+
+    let (start, goal) = (base.id(), target.id());
+    let mut previous: HashMap<CurrencyId, CurrencyId> = HashMap::new();
+    let mut frontier = vec![start];
+    for _ in 0..max_hops {
+        let mut next = Vec::new();
+        for id in frontier {
+            for &neighbour in edges.get(&id).into_iter().flatten() {
+                if neighbour == start || previous.contains_key(&neighbour) {
+                    continue;
+                }
+                previous.insert(neighbour, id);
+                if neighbour == goal {
+                    return resolve_path(base, target, &previous, registry);
+                }
+                next.push(neighbour);
+            }
+        }
+        frontier = next;
+    }
+    None
 }
 
+fn resolve_path(
+    base: &Currency,
+    target: &Currency,
+    previous: &HashMap<CurrencyId, CurrencyId>,
+    registry: &CurrencyRegistry
+) -> Option<Vec<Currency>> {
+    let mut path = vec![target.clone()];
+    let mut id = previous.get(&target.id()).copied()?;
+    while id != base.id() {
+        path.push(registry.get(&id.code()?)?);
+        id = previous.get(&id).copied()?;
+    }
+    path.push(base.clone());
+    path.reverse();
+    Some(path)
+}
 
-/// High-performance trait for exchange rate providers
+/// High-performance trait for exchange rate providers.
+///
+/// A provider that does not quote a pair returns `ExchangeError::NoRateFound`; any other
+/// error (typically `ProviderError`) means the provider itself failed, which lets
+/// `CurrencyConversion` report it instead of silently falling through.
 pub trait ExchangeRateProvider<T: Monetizable + Send + Sync>: Send + Sync
  {
     fn get_exchange_rate(
         &self, 
         base_currency: &Currency, 
         target_currency: &Currency
-    ) -> Option<ExchangeRate<T>>;
+    ) -> Result<ExchangeRate<T>, ExchangeError>;
+
+    /// Rate in effect at `as_of`. Providers without history return their current rate
+    /// when its validity period covers `as_of`.
+    fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        let rate = self.get_exchange_rate(base_currency, target_currency)?;
+        if rate.is_valid_at(as_of) {
+            Ok(rate)
+        } else {
+            Err(ExchangeError::no_rate_found(base_currency.code(), target_currency.code()))
+        }
+    }
+
+    /// Name reported in errors; defaults to the provider's type name
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+    
+    /// Pairs the provider can quote directly, used to search for cross rates.
+    /// Providers that cannot enumerate their pairs return an empty list.
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        Vec::new()
+    }
     
     /// Batch fetch for better performance
     fn get_multiple_rates(
         &self,
-        pairs: &[CurrencyPair]
+        _pairs: &[CurrencyPair]
     ) -> HashMap<CurrencyPair, ExchangeRate<T>> {
         HashMap::new() // Default empty implementation
     }
@@ -185,20 +659,35 @@ pub trait ExchangeRateProvider<T: Monetizable + Send + Sync>: Send + Sync
 
 
 
-/// High-performance conversion service with fallback providers
+/// High-performance conversion service with fallback providers.
+///
+/// When no provider quotes a pair directly, a cross rate can be derived from the
+/// reverse pair (`with_inverse_rates`), through pivot currencies (`with_pivot`) or by
+/// the shortest chain of known pairs (`with_path_search`), tried in that order.
+///
+/// `convert` and `convert_to` use the mid rate. `convert_with_side` and
+/// `convert_to_with_side` use the bid or ask, either as quoted by the provider or, with
+/// `with_markup`, as the desk's own spread around the provider's mid.
+///
+/// `convert` reuses a rate that carries a ttl until it expires; rates without one are
+/// resolved again on every call.
 pub struct CurrencyConversion<T: Monetizable> {
     providers: Vec<Arc<dyn ExchangeRateProvider<T>>>,
     rate_cache: RwLock<HashMap<CurrencyPair, ExchangeRate<T>>>,
     default_context: MonetaryContext,
+    registry: Option<Arc<CurrencyRegistry>>,
+    inverse_rates: bool,
+    pivots: Vec<Currency>,
+    max_hops: usize,
+    markup: Option<Markup>,
 }
 
+/// Rate lookup against a single provider, current or as of a date
+type RateLookup<'a, T> = dyn Fn(&dyn ExchangeRateProvider<T>, &Currency, &Currency) -> Result<ExchangeRate<T>, ExchangeError> + 'a;
+
 impl<T: Monetizable + Send + Sync> CurrencyConversion<T> {
     pub fn new() -> Self {
-        Self {
-            providers: Vec::new(),
-            rate_cache: RwLock::new(HashMap::new()),
-            default_context: MonetaryContext::default(),
-        }
+        Self::with_context(MonetaryContext::default())
     }
     
     pub fn with_context(context: MonetaryContext) -> Self {
@@ -206,9 +695,55 @@ impl<T: Monetizable + Send + Sync> CurrencyConversion<T> {
             providers: Vec::new(),
             rate_cache: RwLock::new(HashMap::new()),
             default_context: context,
+            registry: None,
+            inverse_rates: false,
+            pivots: Vec::new(),
+            max_hops: 0,
+            markup: None,
         }
     }
 
+    /// Derive a missing rate as 1 / rate of the reverse pair, including for pivot legs
+    /// and path search
+    pub fn with_inverse_rates(mut self) -> Self {
+        self.inverse_rates = true;
+        self
+    }
+
+    /// Convert through `pivot` when no direct rate exists; pivots are tried in the order added
+    pub fn with_pivot(mut self, pivot: Currency) -> Self {
+        self.pivots.push(pivot);
+        self
+    }
+
+    /// Search the pairs the providers report in `known_pairs` for the shortest chain of
+    /// at most `max_hops` rates
+    pub fn with_path_search(mut self, max_hops: usize) -> Self {
+        self.max_hops = max_hops;
+        self
+    }
+
+    /// Resolve currency codes against `registry` instead of the global one
+    pub fn with_registry(mut self, registry: Arc<CurrencyRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Quote bid and ask as `markup` around the provider's mid, replacing any quotes
+    /// the provider gives. Applies to the final rate, so a cross rate is marked up once.
+    pub fn with_markup(mut self, markup: Markup) -> Self {
+        self.markup = Some(markup);
+        self
+    }
+
+    pub fn markup(&self) -> Option<Markup> {
+        self.markup
+    }
+
+    pub fn registry(&self) -> &CurrencyRegistry {
+        self.registry.as_deref().unwrap_or(CurrencyRegistry::global())
+    }
+
     pub fn default_context(&self) ->  &MonetaryContext{
         &self.default_context
     }
@@ -224,41 +759,200 @@ impl<T: Monetizable + Send + Sync> CurrencyConversion<T> {
         target_currency: &Currency
     ) -> Result<Monetary<T>, ExchangeError> {
         // Fast path: same currency
-        if amount.currency.numeric_code() == 
-           target_currency.numeric_code() {
+        if amount.currency.same_currency(target_currency) {
             return Ok(amount.clone());
         }
 
-        let pair = CurrencyPair::new(&amount.currency, target_currency);
-        
+        self.with_current_rate(&amount.currency, target_currency, |rate| rate.apply(amount))
+    }
+
+    /// Run `apply` with the cached rate for the pair while it is fresh, otherwise with a
+    /// newly resolved one, shared by `convert` and `convert_with_receipt`
+    pub(crate) fn with_current_rate<R>(
+        &self,
+        base: &Currency,
+        target: &Currency,
+        apply: impl Fn(&ExchangeRate<T>) -> Result<R, ExchangeError>
+    ) -> Result<R, ExchangeError> {
+        let pair = CurrencyPair::new(base, target);
+
         // Check cache first
         {
             let cache = self.rate_cache.read().unwrap();
             if let Some(rate) = cache.get(&pair) {
                 if !rate.is_expired() {
-                    return rate.apply(amount);
+                    return apply(rate);
                 }
             }
         }
 
-        // Try providers in order
-        for provider in &self.providers {
-            if let Some(rate) = provider.get_exchange_rate(&amount.currency, target_currency) {
-                let result = rate.apply(amount);
-                
-                // Cache successful rate
-                if result.is_ok() {
-                    let mut cache = self.rate_cache.write().unwrap();
-                    cache.insert(pair, rate);
+        // Try providers in order, then cross rates
+        let rate = self.get_rate(base, target)?;
+        let result = apply(&rate);
+
+        // Cache successful rates that say how long they stay current. A rate without a
+        // ttl is looked up again, so a provider that reloads its rates is seen at once.
+        if result.is_ok() && rate.get_ttl().is_some() {
+            let mut cache = self.rate_cache.write().unwrap();
+            cache.insert(pair, rate);
+        }
+
+        result
+    }
+
+    /// Rate from `base` to `target`: the first provider quoting the pair, otherwise a
+    /// cross rate derived as configured
+    pub fn get_rate(&self, base: &Currency, target: &Currency) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.find_rate(base, target, &|provider, base, target| provider.get_exchange_rate(base, target))
+    }
+
+    /// Rate from `base` to `target` in effect at `as_of`
+    pub fn get_rate_at(
+        &self,
+        base: &Currency,
+        target: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.find_rate(base, target, &|provider, base, target| provider.get_exchange_rate_at(base, target, as_of))
+    }
+
+    fn find_rate(&self, base: &Currency, target: &Currency, lookup: &RateLookup<T>) -> Result<ExchangeRate<T>, ExchangeError> {
+        let direct = self.find_leg(base, target, lookup);
+        if direct.is_ok() {
+            return direct;
+        }
+
+        for pivot in &self.pivots {
+            if pivot.same_currency(base) || pivot.same_currency(target) {
+                continue;
+            }
+            if let (Ok(first), Ok(second)) = (self.find_leg(base, pivot, lookup), self.find_leg(pivot, target, lookup)) {
+                return first.combine(&second);
+            }
+        }
+
+        if self.max_hops > 1 {
+            if let Some(path) = self.shortest_path(base, target) {
+                let legs = path
+                    .windows(2)
+                    .map(|step| self.find_leg(&step[0], &step[1], lookup))
+                    .collect::<Result<Vec<_>, _>>();
+                if let Some((first, rest)) = legs.as_deref().ok().and_then(<[_]>::split_first) {
+                    return rest.iter().try_fold(first.clone(), |rate, leg| rate.combine(leg));
                 }
-                
-                return result;
             }
         }
 
-        Err(ExchangeError::NoRateFound)
+        // No cross rate either: report why the direct lookup failed
+        direct
+    }
+
+    /// Direct rate from the first provider quoting the pair, or the inverse of the reverse pair
+    fn find_leg(&self, base: &Currency, target: &Currency, lookup: &RateLookup<T>) -> Result<ExchangeRate<T>, ExchangeError> {
+        match self.query_providers(base, target, lookup) {
+            Err(direct) if self.inverse_rates => self
+                .query_providers(target, base, lookup)
+                .and_then(|rate| rate.inverse())
+                .map_err(|reverse| {
+                    // A failing provider matters more than a pair nobody quotes
+                    if direct.is_no_rate_found() && !reverse.is_no_rate_found() { reverse } else { direct }
+                }),
+            result => result,
+        }
+    }
+
+    /// Rate from the first provider that has one, labelled with its name unless the
+    /// provider set one; when none has, every provider's error is collected so failures
+    /// are not hidden behind "no rate"
+    fn query_providers(&self, base: &Currency, target: &Currency, lookup: &RateLookup<T>) -> Result<ExchangeRate<T>, ExchangeError> {
+        let mut errors = Vec::new();
+        for provider in &self.providers {
+            match lookup(provider.as_ref(), base, target) {
+                Ok(rate) if rate.provider.is_some() => return Ok(rate),
+                Ok(rate) => return Ok(rate.with_provider(provider.name())),
+                Err(e) => errors.push(e),
+            }
+        }
+        Err(ExchangeError::from_provider_errors(base.code(), target.code(), errors))
+    }
+
+    /// Breadth-first search over the providers' known pairs; intermediate currencies
+    /// are resolved through the registry
+    fn shortest_path(&self, base: &Currency, target: &Currency) -> Option<Vec<Currency>> {
+        let pairs: Vec<CurrencyPair> = self.providers.iter().flat_map(|provider| provider.known_pairs()).collect();
+        shortest_path(&pairs, self.inverse_rates, self.max_hops, base, target, self.registry())
     }
     
+    /// Convert with the rate in effect at `as_of`. Historical rates bypass the cache.
+    pub fn convert_at(
+        &self,
+        amount: &Monetary<T>,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<Monetary<T>, ExchangeError> {
+        if amount.currency.same_currency(target_currency) {
+            return Ok(amount.clone());
+        }
+
+        self.get_rate_at(&amount.currency, target_currency, as_of)?.apply(amount)
+    }
+
+    /// Convert at `side`, reporting the spread revenue against the mid. Converting to
+    /// the same currency earns nothing.
+    pub fn convert_with_side(
+        &self,
+        amount: &Monetary<T>,
+        target_currency: &Currency,
+        side: RateSide
+    ) -> Result<SpreadConversion<T>, ExchangeError> {
+        if amount.currency.same_currency(target_currency) {
+            return Ok(SpreadConversion {
+                converted: amount.clone(),
+                spread_revenue: Monetary::new(T::zero(), target_currency.clone()),
+                side,
+            });
+        }
+
+        self.quoted_rate(&amount.currency, target_currency)?.apply_side(amount, side)
+    }
+
+    /// Cross-type conversion at `side`, rounded with the rate's context
+    pub fn convert_to_with_side<U: Monetizable>(
+        &self,
+        amount: &Monetary<T>,
+        target_currency: &Currency,
+        side: RateSide
+    ) -> Result<SpreadConversion<U>, ExchangeError> {
+        if amount.currency.same_currency(target_currency) {
+            return Ok(SpreadConversion {
+                converted: self.convert_to(amount, target_currency)?,
+                spread_revenue: Monetary::new(U::zero(), target_currency.clone()),
+                side,
+            });
+        }
+
+        self.quoted_rate(&amount.currency, target_currency)?.apply_convert_side::<U>(amount, side)
+    }
+
+    /// Current rate with the configured markup applied
+    fn quoted_rate(&self, base: &Currency, target: &Currency) -> Result<ExchangeRate<T>, ExchangeError> {
+        let rate = self.get_rate(base, target)?;
+        match self.markup {
+            Some(markup) => rate.with_markup(markup),
+            None => Ok(rate),
+        }
+    }
+
+    /// Convert to the currency registered under `target_code`
+    pub fn convert_to_code(
+        &self,
+        amount: &Monetary<T>,
+        target_code: &str
+    ) -> Result<Monetary<T>, ExchangeError> {
+        let target_currency = self.registry().get(target_code).ok_or_else(|| ExchangeError::unknown_currency(target_code))?;
+        self.convert(amount, &target_currency)
+    }
+
     /// Cross-type conversion with rounding
     pub fn convert_to<U: Monetizable>(
         &self,
@@ -266,24 +960,14 @@ impl<T: Monetizable + Send + Sync> CurrencyConversion<T> {
         target_currency: &Currency
     ) -> Result<Monetary<U>, ExchangeError> {
         // Fast path: same currency, just convert type
-        if amount.currency.numeric_code() == 
-           target_currency.numeric_code() {
-            let converted_amount = U::try_from_decimal(
-                amount.amount.try_to_decimal().map_err(|_|ExchangeError::ConversionError)?
-            ).map_err(|_|ExchangeError::ConversionError)?;
+        if amount.currency.same_currency(target_currency) {
+            let converted_amount = amount.amount.try_to_decimal()
+                .and_then(U::try_from_decimal)
+                .map_err(|e| ExchangeError::conversion_error(amount.currency.code(), target_currency.code(), e))?;
             return Ok(Monetary::new(converted_amount, target_currency.clone()));
         }
 
-        let pair = CurrencyPair::new(&amount.currency, target_currency);
-        
-        // Try providers in order
-        for provider in &self.providers {
-            if let Some(rate) = provider.get_exchange_rate(&amount.currency, target_currency) {
-                return rate.apply_convert::<U>(amount);
-            }
-        }
-
-        Err(ExchangeError::NoRateFound)
+        self.get_rate(&amount.currency, target_currency)?.apply_convert::<U>(amount)
     }
     
     /// Batch conversion for better performance
@@ -293,18 +977,21 @@ impl<T: Monetizable + Send + Sync> CurrencyConversion<T> {
         target_currency: &Currency
     ) -> Vec<Result<Monetary<T>, ExchangeError>> {
         // Group by source currency for efficient batch processing
-        let mut by_currency: HashMap<i32, Vec<usize>> = HashMap::new();
+        let mut by_currency: HashMap<CurrencyId, Vec<usize>> = HashMap::new();
         for (idx, amount) in amounts.iter().enumerate() {
             by_currency
-                .entry(amount.currency.numeric_code())
-                .or_insert_with(Vec::new)
+                .entry(amount.currency.id())
+                .or_default()
                 .push(idx);
         }
         
-        let mut results = vec![Err(ExchangeError::NoRateFound); amounts.len()];
+        let mut results: Vec<Result<Monetary<T>, ExchangeError>> = amounts
+            .iter()
+            .map(|amount| Err(ExchangeError::no_rate_found(amount.currency.code(), target_currency.code())))
+            .collect();
         
-        for (currency_code, indices) in by_currency {
-            if currency_code == target_currency.numeric_code() {
+        for (currency_id, indices) in by_currency {
+            if currency_id == target_currency.id() {
                 // Same currency - no conversion needed
                 for &idx in &indices {
                     results[idx] = Ok(amounts[idx].clone());
@@ -354,14 +1041,15 @@ impl<T: Monetizable + Send + Sync> Default for CurrencyConversion<T> {
 
 /// Extension trait to add conversion methods directly to Monetary
 pub trait MoneyConversion<T: Monetizable> {
-    fn convert_with_rate(&self, rate: T, target_currency: Currency) -> Monetary<T>;
+    fn convert_with_rate(&self, rate: T, target_currency: Currency) -> Result<Monetary<T>, ExchangeError>;
     fn convert_to_type<U: Monetizable>(&self, rate: T, target_currency: Currency) -> Result<Monetary<U>, ExchangeError>;
 }
 
 impl<T: Monetizable> MoneyConversion<T> for Monetary<T> {
-    fn convert_with_rate(&self, rate: T, target_currency: Currency) -> Monetary<T> {
-        let new_amount = self.amount * rate;
-        Monetary::new(new_amount, target_currency)
+    fn convert_with_rate(&self, rate: T, target_currency: Currency) -> Result<Monetary<T>, ExchangeError> {
+        let new_amount = self.amount.checked_mul(rate)
+            .map_err(|e| ExchangeError::conversion_error(self.currency.code(), target_currency.code(), e))?;
+        Ok(Monetary::new(new_amount, target_currency))
     }
     
     fn convert_to_type<U: Monetizable>(
@@ -369,16 +1057,19 @@ impl<T: Monetizable> MoneyConversion<T> for Monetary<T> {
     rate: T,
     target_currency: Currency,
 ) -> Result<Monetary<U>, ExchangeError> {
+    let conversion_error = |e| ExchangeError::conversion_error(self.currency.code(), target_currency.code(), e);
+
     // Safely convert both amount and rate to decimal
     let amount_decimal = self.amount.try_to_decimal()
-        .map_err(|_| ExchangeError::ConversionError)?;
+        .map_err(conversion_error)?;
     let rate_decimal = rate.try_to_decimal()
-        .map_err(|_| ExchangeError::ConversionError)?;
+        .map_err(conversion_error)?;
 
-    let result_decimal = amount_decimal * rate_decimal;
+    let result_decimal = Monetizable::checked_mul(amount_decimal, rate_decimal)
+        .map_err(conversion_error)?;
 
     let new_amount = U::try_from_decimal(result_decimal)
-        .map_err(|_| ExchangeError::ConversionError)?;
+        .map_err(conversion_error)?;
 
     Ok(Monetary::new(new_amount, target_currency))
 }
@@ -1,13 +1,12 @@
 /// Simple implementation of exchange rate provider
-use crate::core::{Monetary, Monetizable, MonetaryContext};
+use crate::core::Monetizable;
 use crate::core::currency::Currency;
-use crate::core::currency_unit::CurrencyUnit;
-use crate::constants::RoundingMode;
 use std::sync::Arc;
 use std::collections::HashMap;
 use std::sync::RwLock;
-use std::time::{Duration, Instant};
-use rust_decimal::Decimal;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use crate::errors::ExchangeError;
 use crate::exchange::base_exchange::{ExchangeRateProvider, CurrencyPair, ExchangeRate};
 
 /// Fast in-memory cache with automatic cleanup
@@ -41,7 +40,7 @@ impl<T: Monetizable + Send + Sync> ExchangeRateProvider<T> for CachedExchangeRat
         &self, 
         base_currency: &Currency, 
         target_currency: &Currency
-    ) -> Option<ExchangeRate<T>> {
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
         let pair = CurrencyPair::new(base_currency, target_currency);
         
         // Fast read path
@@ -49,27 +48,45 @@ impl<T: Monetizable + Send + Sync> ExchangeRateProvider<T> for CachedExchangeRat
             let cache = self.cache.read().unwrap();
             if let Some(rate) = cache.get(&pair) {
                 if !rate.is_expired() {
-                    return Some(rate.clone());
+                    return Ok(rate.clone());
                 }
             }
         }
         
         // Slow path: fetch from upstream and cache
-        if let Some(rate) = self.upstream_provider.get_exchange_rate(base_currency, target_currency) {
-            let  _rate = rate.clone().with_ttl(self.default_ttl);
-            
+        let rate = self.upstream_provider.get_exchange_rate(base_currency, target_currency)?;
+        let  _rate = rate.clone().with_ttl(self.default_ttl);
+        
+        let needs_cleanup = {
             let mut cache = self.cache.write().unwrap();
             cache.insert(pair, _rate);
-            
-            // Periodic cleanup (every 100th access)
-            if cache.len() % 100 == 0 {
-                cache.retain(|_, r| !r.is_expired());
-            }
-            
-            Some(rate)
-        } else {
-            None
+            cache.len().is_multiple_of(100)
+        };
+        
+        // Periodic cleanup (every 100th access)
+        if needs_cleanup {
+            self.cleanup_expired();
         }
+        
+        Ok(rate)
+    }
+
+    // Past rates are not cached; they come straight from upstream
+    fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.upstream_provider.get_exchange_rate_at(base_currency, target_currency, as_of)
+    }
+
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        self.upstream_provider.known_pairs()
+    }
+
+    fn name(&self) -> &str {
+        self.upstream_provider.name()
     }
 }
 
@@ -0,0 +1,341 @@
+// Central bank reference rates
+// ============================
+//
+// Offline reader for the European Central Bank's eurofxref XML documents: the daily
+// file and the 90-day and full histories. Each `<Cube time="...">` is one fixing
+// day, quoting how much of each currency one euro buys:
+//
+//     <Cube time="2024-01-31">
+//         <Cube currency="USD" rate="1.0837"/>
+//         <Cube currency="JPY" rate="159.97"/>
+//     </Cube>
+//
+// Element namespaces are ignored, so other central banks publishing the same layout
+// against their own currency can be read with `with_base`. Inverse rates and cross
+// rates between two quoted currencies are derived through the base currency from the
+// same fixing day, and carry the pairs they were derived from in their chain.
+//
+// A fixing is in effect from midnight UTC of its day until the next fixing, so a
+// weekend lookup returns Friday's rates. Loading several documents merges them, which
+// lets a daily file extend a history; a later document replaces a day it repeats.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use roxmltree::{Document, Node};
+use rust_decimal::Decimal;
+use crate::core::currency::Currency;
+use crate::core::registry::CurrencyRegistry;
+use crate::errors::{ConfigError, Error, ExchangeError};
+use crate::exchange::base_exchange::{CurrencyPair, ExchangeRate, ExchangeRateProvider};
+use crate::exchange::static_exchange::StaticRateProvider;
+
+/// Reference rates read from eurofxref-style XML, one fixing per day
+pub struct EcbRateProvider {
+    base: Currency,
+    registry: Option<Arc<CurrencyRegistry>>,
+    fixings: BTreeMap<NaiveDate, StaticRateProvider<Decimal>>,
+}
+
+impl EcbRateProvider {
+    /// Empty provider quoting against EUR
+    pub fn new() -> Self {
+        Self::with_base(Currency::eur())
+    }
+
+    /// Empty provider for a feed quoting against `base`
+    pub fn with_base(base: Currency) -> Self {
+        Self {
+            base,
+            registry: None,
+            fixings: BTreeMap::new(),
+        }
+    }
+
+    /// Validate currency codes against `registry` instead of the global one
+    pub fn with_registry(mut self, registry: Arc<CurrencyRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Provider holding the fixings of one EUR document
+    pub fn from_xml(xml: &str) -> Result<Self, Error> {
+        let mut provider = Self::new();
+        provider.load_xml(xml)?;
+        Ok(provider)
+    }
+
+    /// Provider holding the fixings of one EUR document given as UTF-8 bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut provider = Self::new();
+        provider.load_bytes(bytes)?;
+        Ok(provider)
+    }
+
+    /// Provider holding the fixings of the EUR document at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let mut provider = Self::new();
+        provider.load_file(path)?;
+        Ok(provider)
+    }
+
+    /// Add the fixings of `xml`. A document with any bad fixing is rejected as a whole
+    /// and leaves the provider unchanged.
+    pub fn load_xml(&mut self, xml: &str) -> Result<(), Error> {
+        let document = Document::parse(xml).map_err(|e| ConfigError::parse("XML", e.to_string()))?;
+        let registry = self.registry.as_deref().unwrap_or(CurrencyRegistry::global());
+
+        let mut fixings = BTreeMap::new();
+        for day in document.descendants().filter(|node| is_cube(node) && node.has_attribute("time")) {
+            let (date, rates) = self.read_fixing(day, registry)?;
+            fixings.insert(date, rates);
+        }
+        if fixings.is_empty() {
+            return Err(ConfigError::parse("XML", "no <Cube time=\"...\"> fixings found").into());
+        }
+        self.fixings.extend(fixings);
+        Ok(())
+    }
+
+    /// Add the fixings of a UTF-8 encoded document
+    pub fn load_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let xml = std::str::from_utf8(bytes).map_err(|e| ConfigError::parse("XML", e.to_string()))?;
+        self.load_xml(xml)
+    }
+
+    /// Add the fixings of the document at `path`
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+        let xml = fs::read_to_string(path).map_err(|e| ConfigError::io(path.display().to_string(), e.to_string()))?;
+        self.load_xml(&xml)
+    }
+
+    pub fn base(&self) -> &Currency {
+        &self.base
+    }
+
+    /// Fixing days held, oldest first
+    pub fn dates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        self.fixings.keys().copied()
+    }
+
+    pub fn latest_date(&self) -> Option<NaiveDate> {
+        self.fixings.keys().next_back().copied()
+    }
+
+    fn read_fixing(&self, day: Node, registry: &CurrencyRegistry) -> Result<(NaiveDate, StaticRateProvider<Decimal>), Error> {
+        let time = day.attribute("time").unwrap_or_default();
+        let date = NaiveDate::from_str(time)
+            .map_err(|_| ConfigError::parse("XML", format!("'{}' is not a fixing date", time)))?;
+
+        let mut rates = StaticRateProvider::new();
+        for quote in day.children().filter(is_cube) {
+            let attribute = |name: &str| {
+                quote.attribute(name).ok_or_else(|| ConfigError::parse("XML", format!("{}: quote without '{}'", date, name)))
+            };
+            let (code, rate) = (attribute("currency")?, attribute("rate")?);
+            let currency = registry.get(code).ok_or_else(|| ExchangeError::unknown_currency(code))?;
+            let invalid = |reason: String| ExchangeError::invalid_rate(self.base.code(), currency.code(), reason);
+
+            let rate = Decimal::from_str(rate)
+                .map_err(|_| invalid(format!("{}: '{}' is not a decimal number", date, rate)))?;
+            if rate <= Decimal::ZERO {
+                return Err(invalid(format!("{}: rate {} must be positive", date, rate)).into());
+            }
+            rates.add_rate(&self.base, &currency, rate);
+        }
+        Ok((date, rates))
+    }
+
+    /// Rate from the fixing in effect at `as_of`, derived through the base currency
+    /// when neither side is the base
+    fn lookup(&self, base: &Currency, target: &Currency, as_of: DateTime<Utc>) -> Result<ExchangeRate<Decimal>, ExchangeError> {
+        let no_rate = || ExchangeError::no_rate_found(base.code(), target.code());
+        if base.same_currency(target) {
+            return Err(no_rate());
+        }
+        let (date, rates) = self.fixings.range(..=as_of.date_naive()).next_back().ok_or_else(no_rate)?;
+        let next = self.fixings.range(date.succ_opt().ok_or_else(no_rate)?..).next().map(|(next, _)| *next);
+
+        let quote = |currency: &Currency| {
+            let rate = rates.get_exchange_rate(&self.base, currency).map_err(|_| no_rate())?;
+            let rate = rate.with_valid_from(midnight(*date));
+            Ok(match next {
+                Some(next) => rate.with_valid_to(midnight(next)),
+                None => rate,
+            })
+        };
+
+        if base.same_currency(&self.base) {
+            quote(target)
+        } else if target.same_currency(&self.base) {
+            quote(base)?.inverse()
+        } else {
+            quote(base)?.inverse()?.combine(&quote(target)?)
+        }
+    }
+}
+
+fn is_cube(node: &Node) -> bool {
+    node.is_element() && node.tag_name().name() == "Cube"
+}
+
+fn midnight(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+impl Default for EcbRateProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExchangeRateProvider<Decimal> for EcbRateProvider {
+    fn get_exchange_rate(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency
+    ) -> Result<ExchangeRate<Decimal>, ExchangeError> {
+        self.get_exchange_rate_at(base_currency, target_currency, Utc::now())
+    }
+
+    fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<Decimal>, ExchangeError> {
+        self.lookup(base_currency, target_currency, as_of)
+    }
+
+    fn name(&self) -> &str {
+        "ecb"
+    }
+
+    /// Pairs quoted in the latest fixing, in both directions. Cross rates between two
+    /// quoted currencies are available too but not listed.
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        self.fixings
+            .values()
+            .next_back()
+            .map(|rates| {
+                rates
+                    .known_pairs()
+                    .into_iter()
+                    .flat_map(|pair| [pair, pair.inverse()])
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const DAILY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/eurofxref-daily.xml");
+    const HISTORY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/eurofxref-hist-90d.xml");
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn at(month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, month, day, 18, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_daily_document() {
+        let provider = EcbRateProvider::from_bytes(&fs::read(DAILY).unwrap()).unwrap();
+        let (eur, usd) = (Currency::eur(), Currency::usd());
+        assert_eq!(provider.latest_date(), NaiveDate::from_ymd_opt(2024, 2, 5));
+
+        let rate = provider.get_exchange_rate(&eur, &usd).unwrap();
+        assert_eq!(*rate.get_factor(), dec("1.0746"));
+        assert_eq!(rate.get_valid_from(), Some(Utc.with_ymd_and_hms(2024, 2, 5, 0, 0, 0).unwrap()));
+        assert!(!rate.is_derived());
+        assert_eq!(provider.known_pairs().len(), 10);
+        assert_eq!(provider.name(), "ecb");
+
+        // Before the only fixing there is nothing to return
+        assert!(provider.get_exchange_rate_at(&eur, &usd, at(2, 2)).unwrap_err().is_no_rate_found());
+        assert!(provider.get_exchange_rate(&eur, &Currency::aud()).unwrap_err().is_no_rate_found());
+    }
+
+    #[test]
+    fn test_inverse_and_cross_rates() {
+        let provider = EcbRateProvider::open(HISTORY).unwrap();
+        let (eur, usd, gbp) = (Currency::eur(), Currency::usd(), Currency::gbp());
+
+        let rate = provider.get_exchange_rate_at(&usd, &eur, at(2, 1)).unwrap();
+        assert_eq!(*rate.get_factor(), Decimal::ONE / dec("1.0814"));
+        assert_eq!(rate.get_chain(), &[CurrencyPair::new(&eur, &usd)]);
+
+        let rate = provider.get_exchange_rate_at(&usd, &gbp, at(2, 1)).unwrap();
+        assert_eq!(*rate.get_factor(), Decimal::ONE / dec("1.0814") * dec("0.85130"));
+        assert_eq!(rate.get_chain(), &[CurrencyPair::new(&eur, &usd), CurrencyPair::new(&eur, &gbp)]);
+        assert_eq!(rate.get_valid_to(), Some(Utc.with_ymd_and_hms(2024, 2, 2, 0, 0, 0).unwrap()));
+
+        let pence = rate.apply(&crate::core::Monetary::new(dec("100"), usd.clone())).unwrap();
+        assert_eq!(pence.currency, gbp);
+        assert!(provider.get_exchange_rate_at(&usd, &usd, at(2, 1)).unwrap_err().is_no_rate_found());
+    }
+
+    #[test]
+    fn test_history_and_merged_daily() {
+        let mut provider = EcbRateProvider::open(HISTORY).unwrap();
+        let (eur, usd) = (Currency::eur(), Currency::usd());
+        let dates: Vec<NaiveDate> = provider.dates().collect();
+        assert_eq!(dates.len(), 3);
+        assert!(dates.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert_eq!(*provider.get_exchange_rate_at(&eur, &usd, at(1, 31)).unwrap().get_factor(), dec("1.0837"));
+        // Saturday uses Friday's fixing
+        assert_eq!(*provider.get_exchange_rate_at(&eur, &usd, at(2, 3)).unwrap().get_factor(), dec("1.0883"));
+
+        provider.load_file(DAILY).unwrap();
+        assert_eq!(provider.dates().count(), 4);
+        let friday = provider.get_exchange_rate_at(&eur, &usd, at(2, 3)).unwrap();
+        assert_eq!(friday.get_valid_to(), Some(Utc.with_ymd_and_hms(2024, 2, 5, 0, 0, 0).unwrap()));
+        assert_eq!(*provider.get_exchange_rate_at(&eur, &usd, at(2, 5)).unwrap().get_factor(), dec("1.0746"));
+        // SEK only appears in the daily file
+        assert!(provider.get_exchange_rate_at(&eur, &Currency::sek(), at(2, 2)).unwrap_err().is_no_rate_found());
+    }
+
+    #[test]
+    fn test_rejected_documents() {
+        let document = |quotes: &str| format!("<Envelope><Cube><Cube time='2024-01-31'>{}</Cube></Cube></Envelope>", quotes);
+        let load = |xml: &str| EcbRateProvider::from_xml(xml).map(|_| ()).unwrap_err();
+
+        assert_eq!(
+            load(&document("<Cube currency='USD' rate='0'/>")),
+            Error::Exchange(ExchangeError::invalid_rate("EUR", "USD", "2024-01-31: rate 0 must be positive"))
+        );
+        assert_eq!(load(&document("<Cube currency='ZZZ' rate='1.5'/>")), Error::Exchange(ExchangeError::unknown_currency("ZZZ")));
+        assert_eq!(load(&document("<Cube currency='USD' rate='abc'/>")).code(), "exchange.invalid_rate");
+        assert_eq!(load(&document("<Cube currency='USD'/>")).code(), "config.parse");
+        assert_eq!(load("<Envelope><Cube>").code(), "config.parse");
+        assert_eq!(load("<Envelope/>").code(), "config.parse");
+        assert_eq!(EcbRateProvider::from_bytes(&[0xff, 0xfe]).map(|_| ()).unwrap_err().code(), "config.parse");
+        assert_eq!(EcbRateProvider::open("/nonexistent/eurofxref.xml").map(|_| ()).unwrap_err().code(), "config.io");
+
+        // A rejected document leaves earlier fixings in place
+        let mut provider = EcbRateProvider::open(DAILY).unwrap();
+        assert!(provider.load_xml(&document("<Cube currency='USD' rate='-1'/>")).is_err());
+        assert_eq!(provider.dates().count(), 1);
+    }
+
+    #[test]
+    fn test_other_central_bank() {
+        let xml = "<Cube><Cube time='2024-01-31'><Cube currency='EUR' rate='0.0863'/><Cube currency='USD' rate='0.0935'/></Cube></Cube>";
+        let mut provider = EcbRateProvider::with_base(Currency::sek());
+        provider.load_xml(xml).unwrap();
+        assert_eq!(provider.base(), &Currency::sek());
+        let rate = provider.get_exchange_rate_at(&Currency::eur(), &Currency::usd(), at(2, 1)).unwrap();
+        assert_eq!(*rate.get_factor(), Decimal::ONE / dec("0.0863") * dec("0.0935"));
+    }
+}
@@ -0,0 +1,452 @@
+// File-backed rates
+// =================
+//
+// Rate tables published as files, in CSV or JSON, picked from the extension:
+//
+//     base,target,rate,valid_from
+//     USD,EUR,0.9215,2024-01-31
+//     GBP,USD,1.27,
+//
+//     [{"base": "USD", "target": "EUR", "rate": "0.9215", "valid_from": "2024-01-31"}]
+//
+// `valid_from` is a date (midnight UTC) or an RFC 3339 timestamp. Dated rows form a
+// history: each is in effect until the next row of the same pair. Rows without one
+// apply at any date. Codes are checked against the registry and rates must be
+// positive; a file with any bad row is rejected as a whole.
+//
+// With `with_hot_reload` the file's modification time and size are checked on lookup,
+// at most once per interval, and the table is re-read when they change. A reload that
+// fails keeps the previous table and is reported by `last_reload_error`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use crate::core::currency::Currency;
+use crate::core::registry::CurrencyRegistry;
+use crate::core::Monetizable;
+use crate::errors::{ConfigError, Error, ExchangeError};
+use crate::exchange::base_exchange::{CurrencyPair, ExchangeRate, ExchangeRateProvider};
+use crate::exchange::historical_exchange::HistoricalRateProvider;
+use crate::exchange::static_exchange::StaticRateProvider;
+
+/// Layout of a rate file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateFileFormat {
+    /// `base,target,rate,valid_from` rows; an optional header row starts with "base"
+    Csv,
+    /// An array of `{"base", "target", "rate", "valid_from"}` objects; rates may be
+    /// strings or numbers
+    Json,
+}
+
+impl RateFileFormat {
+    /// Format for a `.csv` or `.json` path
+    pub fn from_path(path: &Path) -> Result<Self, ConfigError> {
+        match path.extension().and_then(|ext| ext.to_str()).map(|ext| ext.to_ascii_lowercase()).as_deref() {
+            Some("csv") => Ok(RateFileFormat::Csv),
+            Some("json") => Ok(RateFileFormat::Json),
+            _ => Err(ConfigError::unsupported_format(path.display().to_string())),
+        }
+    }
+}
+
+/// One parsed row, before validation
+struct RateRow {
+    line: usize,
+    base: String,
+    target: String,
+    rate: String,
+    valid_from: Option<String>,
+}
+
+/// Rates as loaded from one version of the file
+struct RateTable<T: Monetizable> {
+    dated: HistoricalRateProvider<T>,
+    fixed: StaticRateProvider<T>,
+    pairs: Vec<CurrencyPair>,
+    signature: Option<(SystemTime, u64)>,
+}
+
+/// Exchange rates read from a CSV or JSON file
+pub struct FileRateProvider<T: Monetizable> {
+    path: PathBuf,
+    format: RateFileFormat,
+    registry: Option<Arc<CurrencyRegistry>>,
+    table: RwLock<RateTable<T>>,
+    reload_interval: Option<Duration>,
+    last_check: Mutex<Instant>,
+    last_reload_error: RwLock<Option<Error>>,
+}
+
+impl<T: Monetizable> FileRateProvider<T> {
+    /// Load `path`, validating codes against the global registry
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::load(path.as_ref(), None)
+    }
+
+    /// Load `path`, validating codes against `registry`
+    pub fn open_with_registry(path: impl AsRef<Path>, registry: Arc<CurrencyRegistry>) -> Result<Self, Error> {
+        Self::load(path.as_ref(), Some(registry))
+    }
+
+    fn load(path: &Path, registry: Option<Arc<CurrencyRegistry>>) -> Result<Self, Error> {
+        let format = RateFileFormat::from_path(path)?;
+        let mut provider = Self {
+            path: path.to_path_buf(),
+            format,
+            registry,
+            table: RwLock::new(RateTable::empty()),
+            reload_interval: None,
+            last_check: Mutex::new(Instant::now()),
+            last_reload_error: RwLock::new(None),
+        };
+        provider.table = RwLock::new(provider.read_table()?);
+        Ok(provider)
+    }
+
+    /// Re-read the file when it changes, checking at most once per `interval`
+    pub fn with_hot_reload(mut self, interval: Duration) -> Self {
+        self.reload_interval = Some(interval);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn format(&self) -> RateFileFormat {
+        self.format
+    }
+
+    /// Re-read the file now. On failure the previous rates stay in use.
+    pub fn reload(&self) -> Result<(), Error> {
+        let table = self.read_table()?;
+        *self.table.write().unwrap() = table;
+        *self.last_reload_error.write().unwrap() = None;
+        Ok(())
+    }
+
+    /// Re-read the file if its modification time or size changed since the last load.
+    /// Returns whether it was re-read.
+    pub fn reload_if_changed(&self) -> Result<bool, Error> {
+        let signature = self.signature()?;
+        if self.table.read().unwrap().signature == Some(signature) {
+            return Ok(false);
+        }
+        self.reload().map(|()| true)
+    }
+
+    /// Error of the last failed hot reload, cleared by the next successful one
+    pub fn last_reload_error(&self) -> Option<Error> {
+        self.last_reload_error.read().unwrap().clone()
+    }
+
+    fn refresh(&self) {
+        let Some(interval) = self.reload_interval else {
+            return;
+        };
+        {
+            let mut last_check = self.last_check.lock().unwrap();
+            if last_check.elapsed() < interval {
+                return;
+            }
+            *last_check = Instant::now();
+        }
+        if let Err(err) = self.reload_if_changed() {
+            *self.last_reload_error.write().unwrap() = Some(err);
+        }
+    }
+
+    fn signature(&self) -> Result<(SystemTime, u64), ConfigError> {
+        let display = self.path.display().to_string();
+        let metadata = fs::metadata(&self.path).map_err(|e| ConfigError::io(&display, e.to_string()))?;
+        let modified = metadata.modified().map_err(|e| ConfigError::io(&display, e.to_string()))?;
+        Ok((modified, metadata.len()))
+    }
+
+    fn read_table(&self) -> Result<RateTable<T>, Error> {
+        // Taken before reading, so a write during the read triggers another reload
+        let signature = self.signature()?;
+        let display = self.path.display().to_string();
+        let contents = fs::read_to_string(&self.path).map_err(|e| ConfigError::io(&display, e.to_string()))?;
+        let rows = match self.format {
+            RateFileFormat::Csv => parse_csv(&contents)?,
+            RateFileFormat::Json => parse_json(&contents)?,
+        };
+
+        let registry = self.registry.as_deref().unwrap_or(CurrencyRegistry::global());
+        let mut table = RateTable::empty();
+        table.signature = Some(signature);
+        for row in rows {
+            table.insert(row, registry)?;
+        }
+        Ok(table)
+    }
+}
+
+impl<T: Monetizable> RateTable<T> {
+    fn empty() -> Self {
+        Self {
+            dated: HistoricalRateProvider::new(),
+            fixed: StaticRateProvider::new(),
+            pairs: Vec::new(),
+            signature: None,
+        }
+    }
+
+    fn insert(&mut self, row: RateRow, registry: &CurrencyRegistry) -> Result<(), Error> {
+        let lookup = |code: &str| registry.get(code).ok_or_else(|| ExchangeError::unknown_currency(code));
+        let (base, target) = (lookup(&row.base)?, lookup(&row.target)?);
+        let invalid = |reason: String| ExchangeError::invalid_rate(base.code(), target.code(), reason);
+
+        let rate = Decimal::from_str(&row.rate)
+            .or_else(|_| Decimal::from_scientific(&row.rate))
+            .map_err(|_| invalid(format!("line {}: '{}' is not a decimal number", row.line, row.rate)))?;
+        if rate <= Decimal::ZERO {
+            return Err(invalid(format!("line {}: rate {} must be positive", row.line, rate)).into());
+        }
+        let rate = T::try_from_decimal(rate).map_err(|e| invalid(format!("line {}: {}", row.line, e)))?;
+
+        match row.valid_from.as_deref() {
+            Some(valid_from) => {
+                let valid_from = parse_valid_from(valid_from)
+                    .ok_or_else(|| invalid(format!("line {}: '{}' is not a date or timestamp", row.line, valid_from)))?;
+                self.dated.add_rate(&base, &target, valid_from, rate);
+            }
+            None => self.fixed.add_rate(&base, &target, rate),
+        }
+        let pair = CurrencyPair::new(&base, &target);
+        if !self.pairs.contains(&pair) {
+            self.pairs.push(pair);
+        }
+        Ok(())
+    }
+
+    fn lookup(&self, base: &Currency, target: &Currency, as_of: DateTime<Utc>) -> Result<ExchangeRate<T>, ExchangeError>
+    where
+        T: Send + Sync,
+    {
+        self.dated
+            .get_exchange_rate_at(base, target, as_of)
+            .or_else(|_| self.fixed.get_exchange_rate(base, target))
+    }
+}
+
+fn parse_csv(contents: &str) -> Result<Vec<RateRow>, ConfigError> {
+    let mut rows = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || (rows.is_empty() && line.to_ascii_lowercase().starts_with("base")) {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+        let (base, target, rate, valid_from) = match fields[..] {
+            [base, target, rate] => (base, target, rate, ""),
+            [base, target, rate, valid_from] => (base, target, rate, valid_from),
+            _ => {
+                return Err(ConfigError::parse(
+                    "CSV",
+                    format!("line {}: expected base,target,rate,valid_from but found {} fields", index + 1, fields.len()),
+                ))
+            }
+        };
+        rows.push(RateRow {
+            line: index + 1,
+            base: base.to_string(),
+            target: target.to_string(),
+            rate: rate.to_string(),
+            valid_from: Some(valid_from.to_string()).filter(|v| !v.is_empty()),
+        });
+    }
+    Ok(rows)
+}
+
+fn parse_json(contents: &str) -> Result<Vec<RateRow>, ConfigError> {
+    let document: Value = serde_json::from_str(contents).map_err(|e| ConfigError::parse("JSON", e.to_string()))?;
+    let entries = document
+        .as_array()
+        .ok_or_else(|| ConfigError::parse("JSON", "expected an array of rates"))?;
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let field = |name: &str| match entry.get(name) {
+                Some(Value::String(text)) => Ok(Some(text.clone())),
+                Some(Value::Number(number)) if name == "rate" => Ok(Some(number.to_string())),
+                None | Some(Value::Null) => Ok(None),
+                Some(other) => Err(ConfigError::parse("JSON", format!("rates[{}].{}: unexpected value {}", index, name, other))),
+            };
+            let required = |name: &str| {
+                field(name)?.ok_or_else(|| ConfigError::parse("JSON", format!("rates[{}]: missing '{}'", index, name)))
+            };
+            Ok(RateRow {
+                line: index + 1,
+                base: required("base")?,
+                target: required("target")?,
+                rate: required("rate")?,
+                valid_from: field("valid_from")?,
+            })
+        })
+        .collect()
+}
+
+fn parse_valid_from(text: &str) -> Option<DateTime<Utc>> {
+    NaiveDate::from_str(text)
+        .map(|date| date.and_time(NaiveTime::MIN).and_utc())
+        .or_else(|_| DateTime::parse_from_rfc3339(text).map(|time| time.with_timezone(&Utc)))
+        .ok()
+}
+
+impl<T: Monetizable + Send + Sync> ExchangeRateProvider<T> for FileRateProvider<T> {
+    fn get_exchange_rate(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.get_exchange_rate_at(base_currency, target_currency, Utc::now())
+    }
+
+    fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.refresh();
+        self.table.read().unwrap().lookup(base_currency, target_currency, as_of)
+    }
+
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        self.refresh();
+        self.table.read().unwrap().pairs.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::core::Monetary;
+    use crate::exchange::base_exchange::CurrencyConversion;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn write_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("monetary-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_csv_history_and_fixed_rates() {
+        let path = write_file(
+            "rates.csv",
+            "base,target,rate,valid_from\n\
+             # treasury fixings\n\
+             USD,EUR,0.9201,2024-01-30\n\
+             USD,EUR,0.9215,2024-01-31\n\
+             usd,eur,0.93,2024-02-01T12:00:00Z\n\
+             GBP,USD,1.27,\n\
+             CHF,USD,1.16\n",
+        );
+        let provider = FileRateProvider::<Decimal>::open(&path).unwrap();
+        let (usd, eur) = (Currency::usd(), Currency::eur());
+        let at = |month: u32, day: u32, hour: u32| Utc.with_ymd_and_hms(2024, month, day, hour, 0, 0).unwrap();
+
+        assert_eq!(*provider.get_exchange_rate_at(&usd, &eur, at(1, 31, 18)).unwrap().get_factor(), dec("0.9215"));
+        // The February rate only starts at noon
+        assert_eq!(*provider.get_exchange_rate_at(&usd, &eur, at(2, 1, 11)).unwrap().get_factor(), dec("0.9215"));
+        assert_eq!(*provider.get_exchange_rate(&usd, &eur).unwrap().get_factor(), dec("0.93"));
+        assert_eq!(*provider.get_exchange_rate(&Currency::gbp(), &usd).unwrap().get_factor(), dec("1.27"));
+        assert!(provider.get_exchange_rate_at(&usd, &eur, at(1, 1, 0)).unwrap_err().is_no_rate_found());
+        assert_eq!(provider.known_pairs().len(), 3);
+        assert_eq!(provider.name(), "file");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_json_rates() {
+        let path = write_file(
+            "rates.json",
+            r#"[
+                {"base": "EUR", "target": "JPY", "rate": "161.2", "valid_from": "2024-01-31"},
+                {"base": "EUR", "target": "CHF", "rate": 0.94}
+            ]"#,
+        );
+        let provider = FileRateProvider::<Decimal>::open(&path).unwrap();
+        assert_eq!(provider.format(), RateFileFormat::Json);
+        assert_eq!(*provider.get_exchange_rate(&Currency::eur(), &Currency::jpy()).unwrap().get_factor(), dec("161.2"));
+        assert_eq!(*provider.get_exchange_rate(&Currency::eur(), &Currency::chf()).unwrap().get_factor(), dec("0.94"));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rejected_files() {
+        let open = |name: &str, contents: &str| {
+            let path = write_file(name, contents);
+            let result = FileRateProvider::<Decimal>::open(&path).map(|_| ());
+            fs::remove_file(&path).unwrap();
+            result.unwrap_err()
+        };
+
+        assert_eq!(
+            open("zero.csv", "USD,EUR,0,2024-01-31\n"),
+            Error::Exchange(ExchangeError::invalid_rate("USD", "EUR", "line 1: rate 0 must be positive"))
+        );
+        assert_eq!(open("negative.json", r#"[{"base":"USD","target":"EUR","rate":"-1"}]"#).code(), "exchange.invalid_rate");
+        assert_eq!(open("unknown.csv", "USD,ZZZ,1.5\n"), Error::Exchange(ExchangeError::unknown_currency("ZZZ")));
+        assert_eq!(open("fields.csv", "USD,EUR\n").code(), "config.parse");
+        assert_eq!(open("date.csv", "USD,EUR,1.1,31/01/2024\n").code(), "exchange.invalid_rate");
+        assert_eq!(open("rates.txt", "").code(), "config.unsupported_format");
+        assert_eq!(FileRateProvider::<Decimal>::open("/nonexistent/rates.csv").map(|_| ()).unwrap_err().code(), "config.io");
+    }
+
+    #[test]
+    fn test_hot_reload() {
+        let path = write_file("reload.csv", "USD,EUR,0.92\n");
+        let provider = FileRateProvider::<Decimal>::open(&path).unwrap().with_hot_reload(Duration::ZERO);
+        let (usd, eur) = (Currency::usd(), Currency::eur());
+        assert_eq!(*provider.get_exchange_rate(&usd, &eur).unwrap().get_factor(), dec("0.92"));
+        assert!(!provider.reload_if_changed().unwrap());
+
+        fs::write(&path, "USD,EUR,0.935\nUSD,GBP,0.79\n").unwrap();
+        assert_eq!(*provider.get_exchange_rate(&usd, &eur).unwrap().get_factor(), dec("0.935"));
+        assert_eq!(provider.known_pairs().len(), 2);
+
+        // A broken file keeps the last good table
+        fs::write(&path, "USD,EUR,-3\n").unwrap();
+        assert_eq!(*provider.get_exchange_rate(&usd, &eur).unwrap().get_factor(), dec("0.935"));
+        assert_eq!(provider.last_reload_error().unwrap().code(), "exchange.invalid_rate");
+
+        fs::write(&path, "USD,EUR,0.94\n").unwrap();
+        assert_eq!(*provider.get_exchange_rate(&usd, &eur).unwrap().get_factor(), dec("0.94"));
+        assert_eq!(provider.last_reload_error(), None);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_hot_reload_reaches_conversion() {
+        let path = write_file("reload-conversion.csv", "USD,EUR,0.9\n");
+        let provider = FileRateProvider::<Decimal>::open(&path).unwrap().with_hot_reload(Duration::ZERO);
+        let mut conversion = CurrencyConversion::new();
+        conversion.add_provider(Arc::new(provider));
+
+        let dollars = Monetary::new(dec("100"), Currency::usd());
+        assert_eq!(conversion.convert(&dollars, &Currency::eur()).unwrap().amount, dec("90.00"));
+        fs::write(&path, "USD,EUR,0.95\n").unwrap();
+        assert_eq!(conversion.convert(&dollars, &Currency::eur()).unwrap().amount, dec("95.00"));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,171 @@
+/// In-memory time series of exchange rates for as-of lookups
+use crate::core::{Monetizable, MonetaryContext};
+use crate::core::currency::Currency;
+use crate::errors::ExchangeError;
+use crate::exchange::base_exchange::{ExchangeRateProvider, CurrencyPair, ExchangeRate};
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Bound::{Excluded, Unbounded};
+
+/// Stores a series of observations per currency pair. Each observation is in effect
+/// from its timestamp until the next observation of the same pair, so a weekend
+/// lookup returns Friday's fixing.
+pub struct HistoricalRateProvider<T: Monetizable> {
+    series: HashMap<CurrencyPair, BTreeMap<DateTime<Utc>, T>>,
+    context: MonetaryContext,
+}
+
+impl<T: Monetizable> HistoricalRateProvider<T> {
+    pub fn new() -> Self {
+        Self {
+            series: HashMap::new(),
+            context: MonetaryContext::default(),
+        }
+    }
+
+    pub fn with_context(context: MonetaryContext) -> Self {
+        Self {
+            series: HashMap::new(),
+            context,
+        }
+    }
+
+    /// Record `rate` as in effect from `valid_from`, replacing an observation at the same instant
+    pub fn add_rate(&mut self, base: &Currency, target: &Currency, valid_from: DateTime<Utc>, rate: T) {
+        self.series
+            .entry(CurrencyPair::new(base, target))
+            .or_default()
+            .insert(valid_from, rate);
+    }
+
+    /// Number of observations stored for a pair
+    pub fn len(&self, base: &Currency, target: &Currency) -> usize {
+        self.series
+            .get(&CurrencyPair::new(base, target))
+            .map_or(0, BTreeMap::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.series.values().all(BTreeMap::is_empty)
+    }
+}
+
+impl<T: Monetizable> Default for HistoricalRateProvider<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Monetizable + Send + Sync> ExchangeRateProvider<T> for HistoricalRateProvider<T> {
+    /// Latest rate in effect now
+    fn get_exchange_rate(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        self.get_exchange_rate_at(base_currency, target_currency, Utc::now())
+    }
+
+    fn get_exchange_rate_at(
+        &self,
+        base_currency: &Currency,
+        target_currency: &Currency,
+        as_of: DateTime<Utc>
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
+        let no_rate = || ExchangeError::no_rate_found(base_currency.code(), target_currency.code());
+        let series = self.series.get(&CurrencyPair::new(base_currency, target_currency)).ok_or_else(no_rate)?;
+        let (&valid_from, &rate) = series.range(..=as_of).next_back().ok_or_else(no_rate)?;
+
+        let mut exchange_rate = ExchangeRate::new(base_currency.clone(), target_currency.clone(), rate)
+            .with_valid_from(valid_from)
+            .with_context(self.context.clone());
+        // The next observation, if any, ends this one
+        if let Some((&valid_to, _)) = series.range((Excluded(as_of), Unbounded)).next() {
+            exchange_rate = exchange_rate.with_valid_to(valid_to);
+        }
+        Ok(exchange_rate)
+    }
+
+    fn name(&self) -> &str {
+        "historical"
+    }
+
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        self.series.keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use crate::core::Monetary;
+    use crate::exchange::base_exchange::CurrencyConversion;
+    use crate::exchange::static_exchange::StaticRateProvider;
+
+    fn day(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, 16, 0, 0).unwrap()
+    }
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn ecb_history() -> HistoricalRateProvider<Decimal> {
+        let mut provider = HistoricalRateProvider::new();
+        provider.add_rate(&Currency::eur(), &Currency::usd(), day(2024, 3, 27), dec("1.0826"));
+        provider.add_rate(&Currency::eur(), &Currency::usd(), day(2024, 3, 28), dec("1.0811"));
+        provider.add_rate(&Currency::eur(), &Currency::usd(), day(2024, 4, 2), dec("1.0743"));
+        provider
+    }
+
+    #[test]
+    fn test_rate_in_effect_at() {
+        let provider = ecb_history();
+        let (eur, usd) = (Currency::eur(), Currency::usd());
+        assert_eq!(provider.len(&eur, &usd), 3);
+
+        // Easter weekend: the last fixing before the date applies
+        let rate = provider.get_exchange_rate_at(&eur, &usd, day(2024, 3, 31)).unwrap();
+        assert_eq!(*rate.get_factor(), dec("1.0811"));
+        assert_eq!(rate.get_valid_from(), Some(day(2024, 3, 28)));
+        assert_eq!(rate.get_valid_to(), Some(day(2024, 4, 2)));
+        assert!(rate.is_valid_at(day(2024, 3, 31)));
+        assert!(!rate.is_valid_at(day(2024, 4, 2)));
+
+        // Exactly at an observation, and after the last one
+        let rate = provider.get_exchange_rate_at(&eur, &usd, day(2024, 4, 2)).unwrap();
+        assert_eq!(*rate.get_factor(), dec("1.0743"));
+        assert_eq!(rate.get_valid_to(), None);
+        assert_eq!(*provider.get_exchange_rate(&eur, &usd).unwrap().get_factor(), dec("1.0743"));
+
+        assert_eq!(
+            provider.get_exchange_rate_at(&eur, &usd, day(2024, 1, 1)).err(),
+            Some(ExchangeError::no_rate_found("EUR", "USD"))
+        );
+        assert!(provider.get_exchange_rate_at(&usd, &eur, day(2024, 3, 31)).is_err());
+    }
+
+    #[test]
+    fn test_convert_at_past_date() {
+        let (eur, usd) = (Currency::eur(), Currency::usd());
+        let mut conversion = CurrencyConversion::new();
+        conversion.add_provider(Arc::new(ecb_history()));
+
+        let amount = Monetary::new(dec("1000"), eur.clone());
+        let month_end = conversion.convert_at(&amount, &usd, day(2024, 3, 31)).unwrap();
+        assert_eq!(month_end.amount, dec("1081.1000"));
+        let converted = conversion.convert_at(&amount, &usd, day(2024, 3, 27)).unwrap();
+        assert_eq!(converted.amount, dec("1082.6000"));
+
+        // A static rate has no validity period, so it answers for any date
+        let mut fixed = StaticRateProvider::new();
+        fixed.add_rate(&usd, &eur, dec("0.9"));
+        conversion.add_provider(Arc::new(fixed));
+        let back = conversion.convert_at(&Monetary::new(dec("10"), usd), &eur, day(2001, 1, 1)).unwrap();
+        assert_eq!(back.amount, dec("9.0"));
+    }
+}
@@ -1,5 +1,12 @@
 pub mod base_exchange;
+#[cfg(feature = "async")]
+pub mod async_exchange;
 pub mod cached_exchange;
+#[cfg(feature = "ecb")]
+pub mod ecb_exchange;
+pub mod file_exchange;
+pub mod historical_exchange;
+pub mod receipt;
 pub mod static_exchange;
 
 
@@ -9,7 +16,7 @@ pub mod static_exchange;
 mod tests {
     use super::*;
     use base_exchange::{CurrencyConversion,CurrencyPair};
-    use crate::exchange::base_exchange::{ExchangeRateProvider, ExchangeRate, MoneyConversion};
+    use crate::exchange::base_exchange::{ExchangeRateProvider, ExchangeRate, Markup, MoneyConversion, RateSide};
     use crate::errors::ExchangeError;
     use crate::core::{Monetary, Monetizable, MonetaryContext};
     use crate::core::currency::Currency;
@@ -63,7 +70,7 @@ mod tests {
             &self,
             base_currency: &Currency,
             target_currency: &Currency,
-        ) -> Option<ExchangeRate<T>> {
+        ) -> Result<ExchangeRate<T>, ExchangeError> {
             let mut count = self.call_count.lock().unwrap();
             *count += 1;
 
@@ -74,7 +81,29 @@ mod tests {
                     target_currency.clone(),
                     rate,
                 )
-            })
+            }).ok_or_else(|| ExchangeError::no_rate_found(base_currency.code(), target_currency.code()))
+        }
+    }
+
+    // Provider whose upstream is unreachable
+    struct DownProvider;
+
+    impl ExchangeRateProvider<Decimal> for DownProvider {
+        fn get_exchange_rate(
+            &self,
+            base_currency: &Currency,
+            target_currency: &Currency,
+        ) -> Result<ExchangeRate<Decimal>, ExchangeError> {
+            Err(ExchangeError::provider_error(
+                self.name(),
+                base_currency.code(),
+                target_currency.code(),
+                std::io::Error::new(std::io::ErrorKind::TimedOut, "connection timed out"),
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "rates-api"
         }
     }
 
@@ -199,7 +228,7 @@ mod tests {
         let money = create_test_money(100.0, gbp);
         
         let result = rate.apply(&money);
-        assert_eq!(result.unwrap_err(), ExchangeError::CurrencyMismatch);
+        assert_eq!(result.unwrap_err(), ExchangeError::currency_mismatch("USD", "GBP"));
     }
 
     #[test]
@@ -215,7 +244,13 @@ mod tests {
         thread::sleep(Duration::from_millis(20));
         
         let result = rate.apply(&money);
-        assert_eq!(result.unwrap_err(), ExchangeError::ExpiredRate);
+        match result.unwrap_err() {
+            ExchangeError::ExpiredRate { base, target, expired_at } => {
+                assert_eq!((base.as_str(), target.as_str()), ("USD", "EUR"));
+                assert!(expired_at < chrono::Utc::now());
+            }
+            other => panic!("expected an expired rate, got {}", other),
+        }
     }
 
     #[test]
@@ -252,7 +287,8 @@ mod tests {
         let money = create_test_money(100.0, usd.clone());
         
         let result: Result<Monetary<Decimal>, ExchangeError> = rate.apply_convert(&money);
-        assert!(result.is_ok());
+        assert_eq!(result.unwrap().amount, Decimal::try_from_f64(85.6).unwrap());
+        assert_eq!(rate.apply(&money).unwrap().amount, Decimal::try_from_f64(85.6).unwrap());
         
         // Test Down rounding
         let context_down = MonetaryContext::builder()
@@ -263,7 +299,52 @@ mod tests {
             .with_context(context_down);
         
         let result_down: Result<Monetary<Decimal>, ExchangeError> = rate_down.apply_convert(&money);
-        assert!(result_down.is_ok());
+        assert_eq!(result_down.unwrap().amount, Decimal::try_from_f64(85.5).unwrap());
+
+        // A wider max_scale is kept rather than cut to the currency's minor unit
+        let rate_wide = ExchangeRate::new(usd, eur, Decimal::try_from_f64(0.92154321).unwrap())
+            .with_context(MonetaryContext::builder().with_max_scale(6).build());
+        assert_eq!(rate_wide.apply_convert::<Decimal>(&money).unwrap().amount, Decimal::try_from_f64(92.154321).unwrap());
+    }
+
+    #[test]
+    fn test_currency_rounding_for_every_type() {
+        use std::str::FromStr;
+        use crate::core::minor_units::MinorUnits;
+        use crate::core::types::BigDecimal;
+
+        let (usd, eur, jpy) = (Currency::usd(), Currency::eur(), Currency::jpy());
+        let factor = Decimal::from_str("0.92154").unwrap();
+        let dollars = Monetary::new(Decimal::from_str("10.00").unwrap(), usd.clone());
+        let expected = Decimal::from_str("9.22").unwrap();
+
+        let rate = ExchangeRate::new(usd.clone(), eur.clone(), factor);
+        assert_eq!(rate.apply(&dollars).unwrap().amount, Decimal::from_str("9.2154").unwrap());
+        let rate = rate.with_currency_rounding();
+        assert_eq!(rate.get_rounding_scale(), 2);
+        assert_eq!(rate.apply(&dollars).unwrap().amount, expected);
+        assert_eq!(rate.apply_convert::<Decimal>(&dollars).unwrap().amount, expected);
+        assert_eq!(rate.apply_convert::<f64>(&dollars).unwrap().amount, 9.22);
+        assert_eq!(rate.apply_convert::<MinorUnits>(&dollars).unwrap().amount.to_string(), "9.22");
+        assert_eq!(rate.apply_convert::<BigDecimal>(&dollars).unwrap().amount.to_string(), "9.22");
+
+        let float_rate = ExchangeRate::new(usd.clone(), eur.clone(), 0.92154f64).with_currency_rounding();
+        assert_eq!(float_rate.apply(&Monetary::new(10.0, usd.clone())).unwrap().amount, 9.22);
+
+        let units_rate = ExchangeRate::new(usd.clone(), eur.clone(), MinorUnits::new(92154, 5).unwrap())
+            .with_currency_rounding();
+        let converted = units_rate.apply(&Monetary::from_minor_units(1000i64, usd.clone()).unwrap()).unwrap();
+        assert_eq!(converted.amount.to_string(), "9.22");
+
+        let big_rate = ExchangeRate::new(usd.clone(), eur, BigDecimal::new(92154, 5)).with_currency_rounding();
+        let big_dollars = Monetary::new(BigDecimal::new(1000, 2), usd.clone());
+        assert_eq!(big_rate.apply(&big_dollars).unwrap().amount.to_string(), "9.22");
+
+        // The rounding mode comes from the rate's context, the scale from the currency
+        let down = ExchangeRate::new(usd, jpy, Decimal::from_str("150.99").unwrap())
+            .with_context(MonetaryContext::builder().with_rounding_mode(RoundingMode::Down).build())
+            .with_currency_rounding();
+        assert_eq!(down.apply(&dollars).unwrap().amount, Decimal::from(1509));
     }
 
     #[test]
@@ -291,7 +372,7 @@ mod tests {
         provider.add_rate(&usd, &eur, Decimal::try_from_f64(0.85).unwrap());
         
         let rate = provider.get_exchange_rate(&usd, &gbp);
-        assert!(rate.is_none());
+        assert_eq!(rate.err(), Some(ExchangeError::no_rate_found("USD", "GBP")));
     }
 
     #[test]
@@ -431,7 +512,9 @@ mod tests {
         let money = create_test_money(100.0, usd);
         let result = conversion_service.convert(&money, &eur);
         
-        assert_eq!(result.unwrap_err(), ExchangeError::NoRateFound);
+        let err = result.unwrap_err();
+        assert_eq!(err, ExchangeError::no_rate_found("USD", "EUR"));
+        assert_eq!(err.to_string(), "No exchange rate found for USD/EUR");
     }
 
     #[test]
@@ -499,7 +582,7 @@ mod tests {
         let rate = Decimal::try_from_f64(0.85).unwrap();
         
         let money = create_test_money(100.0, usd);
-        let converted = money.convert_with_rate(rate, eur.clone());
+        let converted = money.convert_with_rate(rate, eur.clone()).unwrap();
         
         assert_eq!(converted.amount, Decimal::try_from_f64(85.0).unwrap());
         assert_eq!(converted.currency, eur);
@@ -522,18 +605,25 @@ mod tests {
 
     #[test]
     fn test_exchange_error_display() {
+        let expired_at = chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap();
         let errors = vec![
-            ExchangeError::CurrencyMismatch,
-            ExchangeError::NoRateFound,
-            ExchangeError::ExpiredRate,
-            ExchangeError::InvalidRate,
-            ExchangeError::ProviderError,
-            ExchangeError::ConversionError,
+            (ExchangeError::currency_mismatch("USD", "GBP"), "Exchange rate converts from 'USD' but the amount is in 'GBP'"),
+            (ExchangeError::no_rate_found("USD", "EUR"), "No exchange rate found for USD/EUR"),
+            (ExchangeError::expired_rate("USD", "EUR", expired_at), "Exchange rate USD/EUR expired at 2023-11-14T22:13:20+00:00"),
+            (ExchangeError::invalid_rate("USD", "EUR", "zero"), "Invalid exchange rate USD/EUR: zero"),
+            (
+                ExchangeError::provider_error("ecb", "USD", "EUR", "HTTP 503"),
+                "Provider 'ecb' failed to fetch USD/EUR: HTTP 503",
+            ),
+            (
+                ExchangeError::conversion_error("USD", "EUR", crate::core::MoneyError::Overflow("multiply".into())),
+                "Cannot convert USD to EUR: Arithmetic overflow in multiply",
+            ),
+            (ExchangeError::unknown_currency("XYZ"), "Unknown currency code: XYZ"),
         ];
         
-        for error in errors {
-            let error_string = format!("{}", error);
-            assert!(!error_string.is_empty());
+        for (error, message) in errors {
+            assert_eq!(error.to_string(), message);
         }
     }
 
@@ -612,7 +702,7 @@ mod tests {
         
         // Cache should still work after concurrent access
         let final_rate = cached_provider.get_exchange_rate(&usd, &eur);
-        assert!(final_rate.is_some());
+        assert!(final_rate.is_ok());
     }
 
     #[test]
@@ -643,4 +733,246 @@ mod tests {
 
         // assert_eq!(conversion_service.convert(100.0, &_eur).to, Decimal::try_from_f64(85.0))
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_currencies_without_numeric_code_are_distinct() {
+        let btc = Currency::btc();
+        let eth = Currency::eth();
+        let usd = Currency::usd();
+        assert_eq!(btc.numeric_code(), eth.numeric_code());
+        assert_ne!(CurrencyPair::new(&btc, &usd), CurrencyPair::new(&eth, &usd));
+        assert!(!btc.same_currency(&eth));
+
+        let mut provider = StaticRateProvider::new();
+        provider.add_rate(&btc, &usd, Decimal::from(60_000));
+        provider.add_rate(&eth, &usd, Decimal::from(3_000));
+
+        let mut conversion = CurrencyConversion::<Decimal>::new();
+        conversion.add_provider(Arc::new(provider));
+
+        let one_btc = Monetary::new(Decimal::ONE, btc.clone());
+        let one_eth = Monetary::new(Decimal::ONE, eth.clone());
+        // Convert BTC first so the cached BTC rate must not be reused for ETH
+        assert_eq!(conversion.convert(&one_btc, &usd).unwrap().amount, Decimal::from(60_000));
+        assert_eq!(conversion.convert(&one_eth, &usd).unwrap().amount, Decimal::from(3_000));
+
+        // BTC -> ETH is a real conversion, not the same-currency fast path
+        assert_eq!(conversion.convert(&one_btc, &eth), Err(ExchangeError::no_rate_found("BTC", "ETH")));
+        assert_eq!(Currency::from_numeric_code(0), None);
+    }
+
+    #[test]
+    fn test_provider_failures_are_reported() {
+        let (usd, eur) = (create_test_currency("USD", 840), create_test_currency("EUR", 978));
+        let money = create_test_money(100.0, usd.clone());
+
+        let mut conversion = CurrencyConversion::<Decimal>::new();
+        conversion.add_provider(Arc::new(DownProvider));
+        conversion.add_provider(Arc::new(StaticRateProvider::new()));
+
+        let err = conversion.convert(&money, &eur).unwrap_err();
+        assert_eq!(err.category(), "AllProvidersFailed");
+        assert!(err.is_recoverable());
+        assert_eq!(
+            err.to_string(),
+            "All 2 exchange rate providers failed for USD/EUR; \
+             Provider 'rates-api' failed to fetch USD/EUR: connection timed out; \
+             No exchange rate found for USD/EUR"
+        );
+        // The underlying I/O error is reachable through the source chain
+        let provider_error = std::error::Error::source(&err).unwrap();
+        let io_error = provider_error.source().unwrap();
+        assert_eq!(io_error.to_string(), "connection timed out");
+
+        // A working fallback still wins
+        let mut fallback = StaticRateProvider::new();
+        fallback.add_rate(&usd, &eur, Decimal::try_from_f64(0.85).unwrap());
+        conversion.add_provider(Arc::new(fallback));
+        assert_eq!(conversion.convert(&money, &eur).unwrap().amount, Decimal::try_from_f64(85.0).unwrap());
+    }
+
+    // EUR-based feed: every rate is quoted as 1 EUR = x
+    fn eur_feed() -> StaticRateProvider<Decimal> {
+        let mut provider = StaticRateProvider::new();
+        provider.add_rate(&Currency::eur(), &Currency::jpy(), Decimal::new(16000, 2));
+        provider.add_rate(&Currency::eur(), &Currency::brl(), Decimal::new(600, 2));
+        provider.add_rate(&Currency::eur(), &Currency::usd(), Decimal::new(108, 2));
+        provider
+    }
+
+    #[test]
+    fn test_cross_rate_through_pivot() {
+        let (eur, jpy, brl) = (Currency::eur(), Currency::jpy(), Currency::brl());
+
+        let mut direct_only = CurrencyConversion::<Decimal>::new();
+        direct_only.add_provider(Arc::new(eur_feed()));
+        let yen = Monetary::new(Decimal::from(16_000), jpy.clone());
+        assert_eq!(direct_only.convert(&yen, &brl), Err(ExchangeError::no_rate_found("JPY", "BRL")));
+
+        let mut conversion = CurrencyConversion::<Decimal>::new()
+            .with_inverse_rates()
+            .with_pivot(eur.clone());
+        conversion.add_provider(Arc::new(eur_feed()));
+
+        // 16,000 JPY = 100 EUR = 600 BRL
+        assert_eq!(conversion.convert(&yen, &brl).unwrap().amount, Decimal::from(600));
+
+        let rate = conversion.get_rate(&jpy, &brl).unwrap();
+        assert!(rate.is_derived());
+        assert_eq!(
+            rate.get_chain(),
+            &[CurrencyPair::new(&eur, &jpy), CurrencyPair::new(&eur, &brl)]
+        );
+
+        // Without inverses the JPY -> EUR leg is missing
+        let mut no_inverse = CurrencyConversion::<Decimal>::new().with_pivot(eur.clone());
+        no_inverse.add_provider(Arc::new(eur_feed()));
+        assert_eq!(no_inverse.convert(&yen, &brl), Err(ExchangeError::no_rate_found("JPY", "BRL")));
+    }
+
+    #[test]
+    fn test_inverse_rate() {
+        let (eur, usd) = (Currency::eur(), Currency::usd());
+        let mut conversion = CurrencyConversion::<Decimal>::new().with_inverse_rates();
+        conversion.add_provider(Arc::new(eur_feed()));
+
+        let dollars = Monetary::new(Decimal::from(108), usd.clone());
+        assert_eq!(conversion.convert(&dollars, &eur).unwrap().amount, Decimal::from(100));
+        let rate = conversion.get_rate(&usd, &eur).unwrap();
+        assert_eq!(rate.get_chain(), &[CurrencyPair::new(&eur, &usd)]);
+        assert!(!conversion.get_rate(&eur, &usd).unwrap().is_derived());
+
+        let zero = ExchangeRate::new(eur.clone(), usd.clone(), Decimal::ZERO);
+        assert_eq!(zero.inverse().err().map(|e| e.category()), Some("InvalidRate"));
+        assert_eq!(
+            zero.combine(&zero).err(),
+            Some(ExchangeError::currency_mismatch("USD", "EUR"))
+        );
+    }
+
+    #[test]
+    fn test_derived_rate_keeps_oldest_fetch_time() {
+        let (eur, usd, jpy) = (Currency::eur(), Currency::usd(), Currency::jpy());
+        let older = ExchangeRate::new(eur.clone(), usd.clone(), Decimal::new(108, 2)).with_ttl(Duration::from_secs(1));
+        thread::sleep(Duration::from_millis(50));
+        let newer = ExchangeRate::new(usd, jpy, Decimal::from(150)).with_ttl(Duration::from_secs(3600));
+        // fetched_at is derived from the monotonic clock, so allow for jitter
+        let close = |a: chrono::DateTime<chrono::Utc>, b: chrono::DateTime<chrono::Utc>| (a - b).num_milliseconds().abs() < 10;
+
+        let inverse = older.inverse().unwrap();
+        assert!(close(inverse.fetched_at(), older.fetched_at()));
+        assert_eq!(inverse.get_ttl(), older.get_ttl());
+
+        // Fetched when the older leg was, expiring with the first leg to expire
+        let cross = older.combine(&newer).unwrap();
+        let cross_fetched = cross.fetched_at();
+        assert!(close(cross_fetched, older.fetched_at()));
+        assert!(newer.fetched_at() - cross_fetched >= chrono::Duration::milliseconds(50));
+        assert_eq!(cross.get_ttl(), older.get_ttl());
+        let reversed = newer.inverse().unwrap().combine(&older.inverse().unwrap()).unwrap();
+        assert_eq!(reversed.get_ttl(), older.get_ttl());
+    }
+
+    #[test]
+    fn test_cross_rate_path_search() {
+        let (gbp, eur, usd, jpy) = (Currency::gbp(), Currency::eur(), Currency::usd(), Currency::jpy());
+        let mut provider = StaticRateProvider::new();
+        provider.add_rate(&gbp, &eur, Decimal::new(115, 2));
+        provider.add_rate(&eur, &usd, Decimal::new(108, 2));
+        provider.add_rate(&usd, &jpy, Decimal::from(150));
+        let provider = Arc::new(provider);
+
+        let mut conversion = CurrencyConversion::<Decimal>::new().with_path_search(3);
+        conversion.add_provider(provider.clone());
+        let pounds = Monetary::new(Decimal::from(100), gbp.clone());
+        assert_eq!(conversion.convert(&pounds, &jpy).unwrap().amount, Decimal::from(18_630));
+        assert_eq!(conversion.get_rate(&gbp, &jpy).unwrap().get_chain().len(), 3);
+
+        // Too few hops, and no reverse edges without inverse rates
+        let mut short = CurrencyConversion::<Decimal>::new().with_path_search(2);
+        short.add_provider(provider.clone());
+        assert_eq!(short.convert(&pounds, &jpy), Err(ExchangeError::no_rate_found("GBP", "JPY")));
+        let yen = Monetary::new(Decimal::from(18_630), jpy.clone());
+        assert_eq!(conversion.convert(&yen, &gbp), Err(ExchangeError::no_rate_found("JPY", "GBP")));
+
+        let mut reverse = CurrencyConversion::<Decimal>::new()
+            .with_inverse_rates()
+            .with_path_search(3);
+        reverse.add_provider(provider);
+        let converted = reverse.convert(&yen, &gbp).unwrap();
+        assert_eq!(converted.amount.round_dp(2), Decimal::from(100));
+        assert_eq!(
+            reverse.get_rate(&jpy, &gbp).unwrap().get_chain(),
+            &[CurrencyPair::new(&usd, &jpy), CurrencyPair::new(&eur, &usd), CurrencyPair::new(&gbp, &eur)]
+        );
+    }
+
+    #[test]
+    fn test_quoted_rate_sides() {
+        let (eur, usd, jpy) = (Currency::eur(), Currency::usd(), Currency::jpy());
+        let rate = ExchangeRate::new(eur.clone(), usd.clone(), Decimal::new(1080, 3))
+            .with_quotes(Decimal::new(1078, 3), Decimal::new(1082, 3));
+        let euros = Monetary::new(Decimal::from(1000), eur.clone());
+
+        let ask = rate.apply_side(&euros, RateSide::Ask).unwrap();
+        assert_eq!(ask.converted.amount, Decimal::from(1082));
+        assert_eq!(ask.spread_revenue, Monetary::new(Decimal::from(2), usd.clone()));
+        let bid = rate.apply_side(&euros, RateSide::Bid).unwrap();
+        assert_eq!((bid.converted.amount, bid.spread_revenue.amount), (Decimal::from(1078), Decimal::from(2)));
+        assert!(rate.apply_side(&euros, RateSide::Mid).unwrap().spread_revenue.is_zero());
+
+        // Inverting swaps the sides; chaining multiplies like sides
+        let inverse = rate.inverse().unwrap();
+        assert_eq!(*inverse.get_bid(), Decimal::ONE / Decimal::new(1082, 3));
+        assert!(inverse.get_bid() < inverse.get_mid() && inverse.get_mid() < inverse.get_ask());
+        let cross = rate.combine(&ExchangeRate::new(usd.clone(), jpy.clone(), Decimal::from(150))).unwrap();
+        assert_eq!((*cross.get_bid(), *cross.get_ask()), (Decimal::new(1617, 1), Decimal::new(1623, 1)));
+
+        // Without quotes every side is the mid
+        let plain = ExchangeRate::new(eur.clone(), usd.clone(), Decimal::new(108, 2));
+        assert!(!plain.has_quotes());
+        assert_eq!(plain.get_side(RateSide::Ask), plain.get_mid());
+
+        let crossed = plain.clone().with_quotes(Decimal::new(109, 2), Decimal::new(110, 2));
+        assert_eq!(crossed.apply_side(&euros, RateSide::Ask).err().map(|e| e.category()), Some("InvalidRate"));
+    }
+
+    #[test]
+    fn test_markup_conversion() {
+        let (eur, usd, jpy) = (Currency::eur(), Currency::usd(), Currency::jpy());
+        let mut conversion = CurrencyConversion::<Decimal>::new()
+            .with_inverse_rates()
+            .with_pivot(eur.clone())
+            .with_markup(Markup::BasisPoints(Decimal::from(50)));
+        conversion.add_provider(Arc::new(eur_feed()));
+        let euros = Monetary::new(Decimal::from(100), eur.clone());
+
+        // 100 EUR at 1.08 +/- 0.5%
+        let customer = conversion.convert_with_side(&euros, &usd, RateSide::Ask).unwrap();
+        assert_eq!(customer.converted.amount, Decimal::new(10854, 2));
+        assert_eq!(customer.spread_revenue, Monetary::new(Decimal::new(54, 2), usd.clone()));
+        let treasury = conversion.convert_with_side(&euros, &usd, RateSide::Mid).unwrap();
+        assert_eq!(treasury.converted, conversion.convert(&euros, &usd).unwrap());
+
+        // A cross rate is marked up once, on its derived mid
+        let dollars = Monetary::new(Decimal::from(108), usd.clone());
+        let yen = conversion.convert_to_with_side::<f64>(&dollars, &jpy, RateSide::Bid).unwrap();
+        assert_eq!(yen.converted.amount, 15920.0);
+        assert_eq!(yen.spread_revenue.amount, 80.0);
+
+        let same = conversion.convert_with_side(&euros, &eur, RateSide::Ask).unwrap();
+        assert_eq!((same.converted, same.spread_revenue.amount), (euros.clone(), Decimal::ZERO));
+
+        assert_eq!(Markup::Percent(Decimal::new(25, 2)).fraction(), Markup::BasisPoints(Decimal::from(25)).fraction());
+        let mut excessive = CurrencyConversion::<Decimal>::new().with_markup(Markup::Percent(Decimal::ONE_HUNDRED));
+        excessive.add_provider(Arc::new(eur_feed()));
+        assert_eq!(
+            excessive.convert_with_side(&euros, &usd, RateSide::Ask).err().map(|e| e.category()),
+            Some("InvalidRate")
+        );
+
+        // A mid too large to mark up is an error, not a panic
+        let huge = ExchangeRate::new(eur, usd, Decimal::MAX).with_markup(Markup::Percent(Decimal::ONE));
+        assert_eq!(huge.err().map(|e| e.category()), Some("ConversionError"));
+    }
+}
@@ -0,0 +1,207 @@
+// Conversion receipts
+// ===================
+//
+// A record of one conversion for audit: both amounts, the exact factor and where it
+// came from, when the rate was fetched and the period it was in effect, and the
+// rounding that produced the target amount. The converted value is rounded with the
+// rate's context, as `convert` does; `residual` keeps what the rounding dropped so
+// that `source * rate == target + residual` holds exactly.
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use crate::core::currency::Currency;
+use crate::core::{Monetary, MonetaryContext, Monetizable};
+use crate::errors::ExchangeError;
+use crate::exchange::base_exchange::{CurrencyConversion, ExchangeRate};
+
+/// How an amount was converted
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionReceipt<T: Monetizable + 'static> {
+    pub source: Monetary<T>,
+    pub target: Monetary<T>,
+    /// Factor applied, before the result was rounded
+    pub rate: T,
+    /// Provider of the rate; `None` for a same-currency conversion or a rate built by hand
+    pub provider: Option<String>,
+    pub rate_timestamp: DateTime<Utc>,
+    pub valid_from: Option<DateTime<Utc>>,
+    pub valid_to: Option<DateTime<Utc>>,
+    /// Context whose scale and rounding mode produced `target`. For a rate with currency
+    /// rounding the scale is the target currency's minor unit.
+    pub context: MonetaryContext,
+    /// Exact converted value minus `target.amount`, in the target currency
+    pub residual: Decimal,
+    pub issued_at: DateTime<Utc>,
+}
+
+impl<T: Monetizable + 'static> ConversionReceipt<T> {
+    /// Exact converted value before rounding
+    pub fn exact_amount(&self) -> Result<Decimal, ExchangeError> {
+        self.target
+            .amount
+            .try_to_decimal()
+            .map(|amount| amount + self.residual)
+            .map_err(|e| ExchangeError::conversion_error(self.source.currency.code(), self.target.currency.code(), e))
+    }
+}
+
+impl<T: Monetizable + 'static> ExchangeRate<T> {
+    /// Convert `amount` as `apply` does and record how
+    pub fn apply_with_receipt(&self, amount: &Monetary<T>) -> Result<ConversionReceipt<T>, ExchangeError> {
+        let target = self.apply(amount)?;
+        let conversion_error = |e| {
+            ExchangeError::conversion_error(self.get_base_currency().code(), self.get_target_currency().code(), e)
+        };
+        let exact = amount
+            .amount
+            .try_to_decimal()
+            .and_then(|amount| Monetizable::checked_mul(amount, self.get_factor().try_to_decimal()?))
+            .map_err(conversion_error)?;
+        let rounded = target.amount.try_to_decimal().map_err(conversion_error)?;
+
+        Ok(ConversionReceipt {
+            source: amount.clone(),
+            target,
+            rate: *self.get_factor(),
+            provider: self.get_provider().map(str::to_string),
+            rate_timestamp: self.fetched_at(),
+            valid_from: self.get_valid_from(),
+            valid_to: self.get_valid_to(),
+            context: MonetaryContext::new(
+                self.get_context().precision(),
+                self.get_rounding_scale(),
+                *self.get_context().rounding_mode()
+            ),
+            residual: (exact - rounded).normalize(),
+            issued_at: Utc::now(),
+        })
+    }
+}
+
+impl<T: Monetizable + Send + Sync + 'static> CurrencyConversion<T> {
+    /// Convert as `convert` does, with the same cached or freshly resolved rate, and
+    /// return a receipt of the conversion. Converting to the same currency records a
+    /// rate of 1 and the service's default context.
+    pub fn convert_with_receipt(
+        &self,
+        amount: &Monetary<T>,
+        target_currency: &Currency
+    ) -> Result<ConversionReceipt<T>, ExchangeError> {
+        if amount.currency.same_currency(target_currency) {
+            let now = Utc::now();
+            return Ok(ConversionReceipt {
+                source: amount.clone(),
+                target: amount.clone(),
+                rate: T::try_from_decimal(Decimal::ONE)
+                    .map_err(|e| ExchangeError::conversion_error(amount.currency.code(), target_currency.code(), e))?,
+                provider: None,
+                rate_timestamp: now,
+                valid_from: None,
+                valid_to: None,
+                context: self.default_context().clone(),
+                residual: Decimal::ZERO,
+                issued_at: now,
+            });
+        }
+
+        self.with_current_rate(&amount.currency, target_currency, |rate| rate.apply_with_receipt(amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+    use chrono::TimeZone;
+    use crate::constants::RoundingMode;
+    use crate::exchange::historical_exchange::HistoricalRateProvider;
+    use crate::exchange::base_exchange::ExchangeRateProvider;
+    use crate::exchange::static_exchange::StaticRateProvider;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    /// Quotes USD/EUR with a one-minute ttl, counting lookups
+    #[derive(Default)]
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    impl ExchangeRateProvider<Decimal> for CountingProvider {
+        fn get_exchange_rate(&self, base: &Currency, target: &Currency) -> Result<ExchangeRate<Decimal>, ExchangeError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(ExchangeRate::new(base.clone(), target.clone(), dec("0.92154")).with_ttl(Duration::from_secs(60)))
+        }
+    }
+
+    #[test]
+    fn test_receipt_records_rate_and_rounding() {
+        let (usd, eur) = (Currency::usd(), Currency::eur());
+        let mut rates = StaticRateProvider::with_context(MonetaryContext::new(19, 2, RoundingMode::HalfUp));
+        rates.add_rate(&usd, &eur, dec("0.92154"));
+        let mut conversion = CurrencyConversion::new();
+        conversion.add_provider(Arc::new(rates));
+
+        let dollars = Monetary::new(dec("10.00"), usd.clone());
+        let receipt = conversion.convert_with_receipt(&dollars, &eur).unwrap();
+        assert_eq!(receipt.source, dollars);
+        assert_eq!(receipt.target, Monetary::new(dec("9.22"), eur.clone()));
+        assert_eq!(receipt.rate, dec("0.92154"));
+        assert_eq!(receipt.provider.as_deref(), Some("static"));
+        assert_eq!(*receipt.context.rounding_mode(), RoundingMode::HalfUp);
+        assert_eq!(receipt.context.max_scale(), 2);
+        assert_eq!(receipt.residual, dec("-0.0046"));
+        assert_eq!(receipt.exact_amount().unwrap(), dec("9.2154"));
+        assert!(receipt.rate_timestamp <= receipt.issued_at);
+        assert_eq!(conversion.convert(&dollars, &eur).unwrap(), receipt.target);
+
+        let same = conversion.convert_with_receipt(&dollars, &usd).unwrap();
+        assert_eq!((same.target, same.rate, same.provider), (dollars.clone(), Decimal::ONE, None));
+        assert_eq!(conversion.convert_with_receipt(&dollars, &Currency::jpy()).err(), Some(ExchangeError::no_rate_found("USD", "JPY")));
+
+        // Currency rounding is recorded as the scale that was applied
+        let rate = ExchangeRate::new(usd, eur, dec("0.92154")).with_currency_rounding();
+        let receipt = rate.apply_with_receipt(&dollars).unwrap();
+        assert_eq!(receipt.target.amount, dec("9.22"));
+        assert_eq!(receipt.context.max_scale(), 2);
+    }
+
+    #[test]
+    fn test_receipt_for_derived_rate() {
+        let (usd, eur, gbp) = (Currency::usd(), Currency::eur(), Currency::gbp());
+        let valid_from = Utc.with_ymd_and_hms(2024, 1, 31, 16, 0, 0).unwrap();
+        let mut history = HistoricalRateProvider::new();
+        history.add_rate(&eur, &usd, valid_from, dec("1.0837"));
+        let mut fixed = StaticRateProvider::new();
+        fixed.add_rate(&eur, &gbp, dec("0.853"));
+
+        let mut conversion = CurrencyConversion::new().with_inverse_rates().with_pivot(eur.clone());
+        conversion.add_provider(Arc::new(history));
+        conversion.add_provider(Arc::new(fixed));
+
+        let receipt = conversion.convert_with_receipt(&Monetary::new(dec("100"), usd.clone()), &gbp).unwrap();
+        assert_eq!(receipt.provider.as_deref(), Some("historical+static"));
+        assert_eq!(receipt.valid_from, Some(valid_from));
+        assert_eq!(receipt.rate, Decimal::ONE / dec("1.0837") * dec("0.853"));
+        assert_eq!(receipt.exact_amount().unwrap(), dec("100") * receipt.rate);
+        assert_eq!(receipt.target.amount, dec("78.711821"));
+    }
+
+    #[test]
+    fn test_receipt_uses_the_conversion_cache() {
+        let (usd, eur) = (Currency::usd(), Currency::eur());
+        let provider = Arc::new(CountingProvider::default());
+        let mut conversion = CurrencyConversion::new();
+        conversion.add_provider(provider.clone());
+
+        let dollars = Monetary::new(dec("10.00"), usd);
+        let converted = conversion.convert(&dollars, &eur).unwrap();
+        let receipt = conversion.convert_with_receipt(&dollars, &eur).unwrap();
+        assert_eq!(receipt.target, converted);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,6 +1,7 @@
 /// Static exchange rate provider for testing/fixed rates
 use crate::core::{Monetizable, MonetaryContext};
 use crate::core::currency::Currency;
+use crate::errors::ExchangeError;
 use crate::exchange::base_exchange::{ExchangeRateProvider, CurrencyPair, ExchangeRate};
 use std::collections::HashMap;
 
@@ -34,12 +35,18 @@ impl<T: Monetizable> StaticRateProvider<T> {
     }
 }
 
+impl<T: Monetizable> Default for StaticRateProvider<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<T: Monetizable + std::marker::Sync + std::marker::Send> ExchangeRateProvider<T> for StaticRateProvider<T> {
     fn get_exchange_rate(
         &self, 
         base_currency: &Currency, 
         target_currency: &Currency
-    ) -> Option<ExchangeRate<T>> {
+    ) -> Result<ExchangeRate<T>, ExchangeError> {
         let pair = CurrencyPair::new(base_currency, target_currency);
         
         self.rates.get(&pair).map(|&rate| {
@@ -48,6 +55,14 @@ impl<T: Monetizable + std::marker::Sync + std::marker::Send> ExchangeRateProvide
                 target_currency.clone(),
                 rate
             ).with_context(self.context.clone())
-        })
+        }).ok_or_else(|| ExchangeError::no_rate_found(base_currency.code(), target_currency.code()))
+    }
+
+    fn name(&self) -> &str {
+        "static"
+    }
+
+    fn known_pairs(&self) -> Vec<CurrencyPair> {
+        self.rates.keys().copied().collect()
     }
 }
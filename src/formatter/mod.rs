@@ -0,0 +1,412 @@
+use std::fmt;
+use std::str::FromStr;
+use rust_decimal::{Decimal, RoundingStrategy};
+use crate::core::{Monetary, Monetizable, MoneyError};
+use crate::core::currency::Currency;
+use crate::core::money::Money;
+use crate::errors::CurrencyError;
+
+/// Where the currency symbol (or code) is placed relative to the number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolPosition {
+    Prefix,
+    Suffix,
+}
+
+/// How negative amounts are rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NegativeStyle {
+    /// Leading minus sign: "-$1,234.56"
+    MinusSign,
+    /// Accounting style: "($1,234.56)"
+    Parentheses,
+}
+
+/// Digit grouping scheme for the integer part
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    /// No grouping: "1234567"
+    None,
+    /// Groups of three: "1,234,567"
+    Thousands,
+    /// Indian lakh/crore grouping, three then twos: "12,34,567"
+    Indian,
+}
+
+/// Whether the currency is shown by its symbol or its ISO code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurrencyDisplay {
+    Symbol,
+    Code,
+}
+
+/// Number and currency layout rules for one locale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocaleStyle {
+    pub grouping_separator: &'static str,
+    pub decimal_separator: &'static str,
+    pub grouping: Grouping,
+    pub symbol_position: SymbolPosition,
+    /// Inserted between the symbol and the number (empty for "$1.00")
+    pub symbol_spacing: &'static str,
+    pub negative_style: NegativeStyle,
+}
+
+const NBSP: &str = "\u{a0}";
+const NARROW_NBSP: &str = "\u{202f}";
+
+/// Locales with built-in formatting rules (based on CLDR currency patterns)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Locale {
+    EnUs,
+    EnGb,
+    DeDe,
+    FrFr,
+    FrCh,
+    JaJp,
+    HiIn,
+    ArAe,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 8] = [
+        Locale::EnUs,
+        Locale::EnGb,
+        Locale::DeDe,
+        Locale::FrFr,
+        Locale::FrCh,
+        Locale::JaJp,
+        Locale::HiIn,
+        Locale::ArAe,
+    ];
+
+    /// BCP 47 language tag, e.g. "de-DE"
+    pub fn tag(&self) -> &'static str {
+        match self {
+            Locale::EnUs => "en-US",
+            Locale::EnGb => "en-GB",
+            Locale::DeDe => "de-DE",
+            Locale::FrFr => "fr-FR",
+            Locale::FrCh => "fr-CH",
+            Locale::JaJp => "ja-JP",
+            Locale::HiIn => "hi-IN",
+            Locale::ArAe => "ar-AE",
+        }
+    }
+
+    pub fn style(&self) -> LocaleStyle {
+        match self {
+            Locale::EnUs | Locale::EnGb | Locale::JaJp => LocaleStyle {
+                grouping_separator: ",",
+                decimal_separator: ".",
+                grouping: Grouping::Thousands,
+                symbol_position: SymbolPosition::Prefix,
+                symbol_spacing: "",
+                negative_style: NegativeStyle::MinusSign,
+            },
+            Locale::DeDe => LocaleStyle {
+                grouping_separator: ".",
+                decimal_separator: ",",
+                grouping: Grouping::Thousands,
+                symbol_position: SymbolPosition::Suffix,
+                symbol_spacing: NBSP,
+                negative_style: NegativeStyle::MinusSign,
+            },
+            Locale::FrFr => LocaleStyle {
+                grouping_separator: NARROW_NBSP,
+                decimal_separator: ",",
+                grouping: Grouping::Thousands,
+                symbol_position: SymbolPosition::Suffix,
+                symbol_spacing: NBSP,
+                negative_style: NegativeStyle::MinusSign,
+            },
+            // Swiss French keeps the French grouping but uses a point as the currency decimal
+            Locale::FrCh => LocaleStyle {
+                grouping_separator: NARROW_NBSP,
+                decimal_separator: ".",
+                grouping: Grouping::Thousands,
+                symbol_position: SymbolPosition::Suffix,
+                symbol_spacing: NBSP,
+                negative_style: NegativeStyle::MinusSign,
+            },
+            Locale::HiIn => LocaleStyle {
+                grouping_separator: ",",
+                decimal_separator: ".",
+                grouping: Grouping::Indian,
+                symbol_position: SymbolPosition::Prefix,
+                symbol_spacing: "",
+                negative_style: NegativeStyle::MinusSign,
+            },
+            // Latin digits, symbol after the number
+            Locale::ArAe => LocaleStyle {
+                grouping_separator: ",",
+                decimal_separator: ".",
+                grouping: Grouping::Thousands,
+                symbol_position: SymbolPosition::Suffix,
+                symbol_spacing: NBSP,
+                negative_style: NegativeStyle::MinusSign,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tag())
+    }
+}
+
+// Accepts "de-DE", "de_DE" and any letter case
+impl FromStr for Locale {
+    type Err = CurrencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().replace('_', "-");
+        Locale::ALL
+            .iter()
+            .find(|locale| locale.tag().eq_ignore_ascii_case(&normalized))
+            .copied()
+            .ok_or_else(|| CurrencyError::invalid_format_with_input("Unsupported locale", s))
+    }
+}
+
+/// Locale-aware renderer for `Monetary<T>` and `Money` amounts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonetaryFormatter {
+    locale: Locale,
+    style: LocaleStyle,
+    currency_display: CurrencyDisplay,
+    precision: Option<u32>,
+}
+
+impl MonetaryFormatter {
+    pub fn new(locale: Locale) -> Self {
+        Self {
+            locale,
+            style: locale.style(),
+            currency_display: CurrencyDisplay::Symbol,
+            precision: None,
+        }
+    }
+
+    /// Create a formatter from a language tag such as "fr-CH"
+    pub fn for_tag(tag: &str) -> Result<Self, CurrencyError> {
+        Ok(Self::new(tag.parse()?))
+    }
+
+    /// Replace the locale's layout rules entirely
+    pub fn with_style(mut self, style: LocaleStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn with_currency_display(mut self, currency_display: CurrencyDisplay) -> Self {
+        self.currency_display = currency_display;
+        self
+    }
+
+    pub fn with_negative_style(mut self, negative_style: NegativeStyle) -> Self {
+        self.style.negative_style = negative_style;
+        self
+    }
+
+    /// Override the number of fraction digits (defaults to the currency's precision)
+    pub fn with_precision(mut self, precision: u32) -> Self {
+        self.precision = Some(precision);
+        self
+    }
+
+    pub fn locale(&self) -> Locale {
+        self.locale
+    }
+
+    pub fn style(&self) -> &LocaleStyle {
+        &self.style
+    }
+
+    pub fn format<T: Monetizable>(&self, money: &Monetary<T>) -> Result<String, MoneyError> {
+        let amount = money.amount.try_to_decimal()?;
+        Ok(self.format_amount(amount, &money.currency))
+    }
+
+    pub fn format_money(&self, money: &Money) -> Result<String, MoneyError> {
+        Ok(self.format_amount(money.amount(), &money.currency()))
+    }
+
+    /// Format a raw decimal amount in the given currency
+    pub fn format_amount(&self, amount: Decimal, currency: &Currency) -> String {
+        let scale = self
+            .precision
+            .unwrap_or_else(|| currency.precision().max(0) as u32);
+        let rounded = amount.round_dp_with_strategy(scale, RoundingStrategy::MidpointNearestEven);
+        let digits = format!("{:.*}", scale as usize, rounded.abs());
+
+        let (integer_part, fraction_part) = match digits.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (digits.as_str(), None),
+        };
+
+        let mut number = self.group_digits(integer_part);
+        if let Some(fraction) = fraction_part {
+            number.push_str(self.style.decimal_separator);
+            number.push_str(fraction);
+        }
+
+        let currency_text = match self.currency_display {
+            CurrencyDisplay::Symbol => currency.symbol(),
+            CurrencyDisplay::Code => currency.code(),
+        };
+        // Codes are letters, so they always need a gap from the digits
+        let spacing = if self.currency_display == CurrencyDisplay::Code && self.style.symbol_spacing.is_empty() {
+            NBSP
+        } else {
+            self.style.symbol_spacing
+        };
+
+        let body = match self.style.symbol_position {
+            SymbolPosition::Prefix => format!("{}{}{}", currency_text, spacing, number),
+            SymbolPosition::Suffix => format!("{}{}{}", number, spacing, currency_text),
+        };
+
+        if rounded.is_sign_negative() && !rounded.is_zero() {
+            match self.style.negative_style {
+                NegativeStyle::MinusSign => format!("-{}", body),
+                NegativeStyle::Parentheses => format!("({})", body),
+            }
+        } else {
+            body
+        }
+    }
+
+    fn group_digits(&self, integer_part: &str) -> String {
+        let separator = self.style.grouping_separator;
+        let digits: Vec<char> = integer_part.chars().collect();
+
+        // Group sizes from the right: Thousands = 3,3,3...; Indian = 3,2,2...
+        let (first, rest) = match self.style.grouping {
+            Grouping::None => return integer_part.to_string(),
+            Grouping::Thousands => (3, 3),
+            Grouping::Indian => (3, 2),
+        };
+
+        let mut groups: Vec<String> = Vec::new();
+        let mut end = digits.len();
+        let mut size = first;
+        while end > size {
+            groups.push(digits[end - size..end].iter().collect());
+            end -= size;
+            size = rest;
+        }
+        groups.push(digits[..end].iter().collect());
+        groups.reverse();
+        groups.join(separator)
+    }
+}
+
+impl Default for MonetaryFormatter {
+    fn default() -> Self {
+        Self::new(Locale::EnUs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::BigDecimal;
+
+    fn usd(amount: &str) -> Monetary<Decimal> {
+        Monetary::new(Decimal::from_str(amount).unwrap(), Currency::usd())
+    }
+
+    #[test]
+    fn test_locale_layouts() {
+        let eur = Monetary::new(Decimal::from_str("1234567.891").unwrap(), Currency::eur());
+        let cases = vec![
+            (Locale::EnUs, "€1,234,567.89"),
+            (Locale::EnGb, "€1,234,567.89"),
+            (Locale::DeDe, "1.234.567,89\u{a0}€"),
+            (Locale::FrFr, "1\u{202f}234\u{202f}567,89\u{a0}€"),
+            (Locale::FrCh, "1\u{202f}234\u{202f}567.89\u{a0}€"),
+            (Locale::JaJp, "€1,234,567.89"),
+            (Locale::HiIn, "€12,34,567.89"),
+            (Locale::ArAe, "1,234,567.89\u{a0}€"),
+        ];
+
+        for (locale, expected) in cases {
+            let formatted = MonetaryFormatter::new(locale).format(&eur).unwrap();
+            assert_eq!(formatted, expected, "Failed for locale: {}", locale);
+        }
+    }
+
+    #[test]
+    fn test_currency_precision_and_rounding() {
+        let jpy = Monetary::new(Decimal::from_str("1234.5").unwrap(), Currency::jpy());
+        assert_eq!(MonetaryFormatter::new(Locale::JaJp).format(&jpy).unwrap(), "¥1,234");
+
+        let kwd = Monetary::new(Decimal::from_str("12.3456").unwrap(), Currency::kwd());
+        assert_eq!(MonetaryFormatter::new(Locale::ArAe).format(&kwd).unwrap(), "12.346\u{a0}KD");
+
+        let padded = MonetaryFormatter::new(Locale::EnUs).format(&usd("5")).unwrap();
+        assert_eq!(padded, "$5.00");
+
+        let custom = MonetaryFormatter::new(Locale::EnUs).with_precision(4).format(&usd("5.1")).unwrap();
+        assert_eq!(custom, "$5.1000");
+    }
+
+    #[test]
+    fn test_negative_styles() {
+        let negative = usd("-1234.56");
+        assert_eq!(MonetaryFormatter::new(Locale::EnUs).format(&negative).unwrap(), "-$1,234.56");
+        assert_eq!(
+            MonetaryFormatter::new(Locale::DeDe).format(&negative.clone().with_currency(Currency::eur())).unwrap(),
+            "-1.234,56\u{a0}€"
+        );
+
+        let accounting = MonetaryFormatter::new(Locale::EnUs).with_negative_style(NegativeStyle::Parentheses);
+        assert_eq!(accounting.format(&negative).unwrap(), "($1,234.56)");
+
+        // Amounts that round to zero are never shown as negative
+        assert_eq!(MonetaryFormatter::new(Locale::EnUs).format(&usd("-0.001")).unwrap(), "$0.00");
+    }
+
+    #[test]
+    fn test_currency_code_display() {
+        let formatter = MonetaryFormatter::new(Locale::EnUs).with_currency_display(CurrencyDisplay::Code);
+        assert_eq!(formatter.format(&usd("12.5")).unwrap(), "USD\u{a0}12.50");
+
+        let chf = Monetary::new(Decimal::from_str("1234.5").unwrap(), Currency::chf());
+        let swiss = MonetaryFormatter::for_tag("fr_ch").unwrap().with_currency_display(CurrencyDisplay::Code);
+        assert_eq!(swiss.format(&chf).unwrap(), "1\u{202f}234.50\u{a0}CHF");
+    }
+
+    #[test]
+    fn test_indian_grouping() {
+        let inr = Monetary::new(Decimal::from_str("123456789.5").unwrap(), Currency::inr());
+        let formatter = MonetaryFormatter::new(Locale::HiIn);
+        assert_eq!(formatter.format(&inr).unwrap(), "₹12,34,56,789.50");
+
+        let small = Monetary::new(Decimal::from_str("999").unwrap(), Currency::inr());
+        assert_eq!(formatter.format(&small).unwrap(), "₹999.00");
+    }
+
+    #[test]
+    fn test_format_other_backends() {
+        let big = Monetary::new(BigDecimal::from_str("9876.5").unwrap(), Currency::gbp());
+        assert_eq!(MonetaryFormatter::new(Locale::EnGb).format(&big).unwrap(), "£9,876.50");
+
+        let float = Monetary::new(1000.25_f64, Currency::usd());
+        assert_eq!(MonetaryFormatter::default().format(&float).unwrap(), "$1,000.25");
+
+        let money = Money::eur(-42.5);
+        assert_eq!(MonetaryFormatter::new(Locale::FrFr).format_money(&money).unwrap(), "-42,50\u{a0}€");
+    }
+
+    #[test]
+    fn test_locale_parsing() {
+        assert_eq!("en-US".parse::<Locale>().unwrap(), Locale::EnUs);
+        assert_eq!("DE_de".parse::<Locale>().unwrap(), Locale::DeDe);
+        assert!("xx-YY".parse::<Locale>().is_err());
+        assert!(MonetaryFormatter::for_tag("pt-BR").is_err());
+        assert_eq!(Locale::HiIn.to_string(), "hi-IN");
+    }
+}
@@ -5,13 +5,17 @@ pub mod configs;
 pub mod formatter;
 pub mod parser;
 pub mod errors;
+pub use errors::Error;
+#[cfg(feature = "serde")]
+pub mod serialization;
 
 pub mod prelude{
     pub use crate::constants::*;
     pub use crate::core::*;
     pub use crate::exchange::*;
     pub use crate::configs::*;
+    pub use crate::formatter::*;
 
 
 
-}
\ No newline at end of file
+}
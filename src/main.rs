@@ -1,5 +1,279 @@
-use monetary::prelude::{RoundingMode,};
+// monetary command-line tool
+// ==========================
+//
+// Conversion, formatting, parsing and allocation from the shell, built with the `cli`
+// feature. Every command prints a human-readable line by default and a JSON document
+// with `--json`; errors are reported with their stable `Error::code`.
+//
+//     monetary convert 100 USD EUR --rates rates.csv --at 2024-01-31
+//     monetary format 1234.5 EUR --locale de-DE
+//     monetary parse '$1,234.56'
+//     monetary currencies list
+//     monetary allocate 100 USD 3
 
-fn main() {
-    println!("Hello, world!");
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::Arc;
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use clap::{Parser, Subcommand};
+use rust_decimal::Decimal;
+use serde_json::{json, Value};
+use monetary::core::currency::Currency;
+use monetary::core::registry::CurrencyRegistry;
+use monetary::core::Monetary;
+use monetary::errors::CurrencyError;
+use monetary::exchange::base_exchange::CurrencyConversion;
+use monetary::exchange::file_exchange::FileRateProvider;
+use monetary::formatter::MonetaryFormatter;
+use monetary::parser::MoneyParser;
+use monetary::Error;
+
+#[derive(Debug, Parser)]
+#[command(name = "monetary", version, about = "Convert, format and parse monetary amounts")]
+struct Cli {
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Convert an amount with rates read from a CSV or JSON file
+    Convert {
+        amount: String,
+        from: String,
+        to: String,
+        /// CSV or JSON rate table, see `FileRateProvider`
+        #[arg(long)]
+        rates: String,
+        /// Use the rates in effect at the end of this day (YYYY-MM-DD)
+        #[arg(long)]
+        at: Option<NaiveDate>,
+    },
+    /// Format an amount for a locale
+    Format {
+        amount: String,
+        currency: String,
+        #[arg(long, default_value = "en-US")]
+        locale: String,
+    },
+    /// Parse an amount such as "$1,234.56" or "EUR 12,50"
+    Parse {
+        text: String,
+        /// Locale whose decimal separator the text uses; detected when omitted
+        #[arg(long)]
+        locale: Option<String>,
+    },
+    /// Inspect the currency registry
+    Currencies {
+        #[command(subcommand)]
+        command: CurrenciesCommand,
+    },
+    /// Split an amount into equal parts that add up to it exactly
+    Allocate {
+        amount: String,
+        currency: String,
+        parts: usize,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum CurrenciesCommand {
+    /// Print every registered currency
+    List,
+}
+
+/// What a command prints, as text and as JSON
+struct Report {
+    text: String,
+    json: Value,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli.command) {
+        Ok(report) if cli.json => {
+            println!("{}", report.json);
+            ExitCode::SUCCESS
+        }
+        Ok(report) => {
+            println!("{}", report.text);
+            ExitCode::SUCCESS
+        }
+        Err(err) if cli.json => {
+            println!("{}", json!({"error": {"code": err.code(), "message": err.to_string()}}));
+            ExitCode::FAILURE
+        }
+        Err(err) => {
+            eprintln!("error [{}]: {}", err.code(), err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<Report, Error> {
+    match command {
+        Command::Convert { amount, from, to, rates, at } => convert(&amount, &from, &to, &rates, at),
+        Command::Format { amount, currency, locale } => format(&amount, &currency, &locale),
+        Command::Parse { text, locale } => parse(&text, locale.as_deref()),
+        Command::Currencies { command: CurrenciesCommand::List } => Ok(list_currencies()),
+        Command::Allocate { amount, currency, parts } => allocate(&amount, &currency, parts),
+    }
+}
+
+fn convert(amount: &str, from: &str, to: &str, rates: &str, at: Option<NaiveDate>) -> Result<Report, Error> {
+    let amount = Monetary::new(decimal(amount)?, Currency::from_str(from)?);
+    let target = Currency::from_str(to)?;
+    let conversion = load_rates(rates)?;
+
+    let rate = match at {
+        Some(date) => conversion.get_rate_at(&amount.currency, &target, end_of_day(date))?,
+        None => conversion.get_rate(&amount.currency, &target)?,
+    };
+    // Show the amount as it would be paid: in the target currency's minor unit
+    let rate = rate.with_currency_rounding();
+    let converted = rate.apply(&amount)?;
+    Ok(Report {
+        text: format!("{} = {} (rate {})", amount, converted, rate.get_factor()),
+        json: json!({
+            "source": amount,
+            "target": converted,
+            "rate": rate.get_factor().to_string(),
+            "as_of": at.map(end_of_day),
+        }),
+    })
+}
+
+fn format(amount: &str, currency: &str, locale: &str) -> Result<Report, Error> {
+    let formatter = MonetaryFormatter::for_tag(locale)?;
+    let amount = Monetary::new(decimal(amount)?, Currency::from_str(currency)?);
+    let formatted = formatter.format(&amount)?;
+    Ok(Report {
+        json: json!({"formatted": formatted, "locale": formatter.locale().tag(), "amount": amount}),
+        text: formatted,
+    })
+}
+
+fn parse(text: &str, locale: Option<&str>) -> Result<Report, Error> {
+    let parser = match locale {
+        Some(tag) => MoneyParser::for_locale(tag.parse()?),
+        None => MoneyParser::new(),
+    };
+    let amount = parser.parse(text)?;
+    Ok(Report {
+        text: amount.to_string(),
+        json: json!(amount),
+    })
+}
+
+fn list_currencies() -> Report {
+    let currencies = CurrencyRegistry::global().currencies();
+    let text = currencies
+        .iter()
+        .map(|c| format!("{:<5} {:>3} {:>2} {:<4} {}", c.code(), c.numeric_code(), c.precision(), c.symbol(), c.display_name()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let json = currencies
+        .iter()
+        .map(|c| json!({
+            "code": c.code(),
+            "numeric_code": c.numeric_code(),
+            "minor_units": c.precision(),
+            "symbol": c.symbol(),
+            "name": c.display_name(),
+            "withdrawn_on": c.withdrawn_on(),
+        }))
+        .collect();
+    Report { text, json: Value::Array(json) }
+}
+
+fn allocate(amount: &str, currency: &str, parts: usize) -> Result<Report, Error> {
+    let amount = Monetary::new(decimal(amount)?, Currency::from_str(currency)?);
+    let split = amount.split(parts)?;
+    Ok(Report {
+        text: split.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"),
+        json: json!(split),
+    })
+}
+
+fn decimal(amount: &str) -> Result<Decimal, CurrencyError> {
+    Decimal::from_str_exact(amount.trim()).map_err(|err| CurrencyError::invalid_amount(amount, err.to_string()))
+}
+
+fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::from_hms_opt(23, 59, 59).expect("valid time")).and_utc()
+}
+
+fn load_rates(path: &str) -> Result<CurrencyConversion<Decimal>, Error> {
+    let mut conversion = CurrencyConversion::new().with_inverse_rates();
+    conversion.add_provider(Arc::new(FileRateProvider::open(path)?));
+    Ok(conversion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn run_args(args: &[&str]) -> Result<Report, Error> {
+        let cli = Cli::try_parse_from(std::iter::once("monetary").chain(args.iter().copied())).unwrap();
+        run(cli.command)
+    }
+
+    fn rates_file(content: &str) -> String {
+        let path = std::env::temp_dir().join(format!("monetary-cli-rates-{}.csv", std::process::id()));
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_convert() {
+        let rates = rates_file(
+            "base,target,rate,valid_from\n\
+             USD,EUR,0.9201,2024-01-30\n\
+             USD,EUR,0.9215,2024-01-31\n\
+             USD,EUR,0.9300,2024-02-01\n\
+             GBP,USD,1.27\n",
+        );
+        let report = run_args(&["convert", "100", "USD", "EUR", "--rates", &rates, "--at", "2024-01-31"]).unwrap();
+        assert_eq!(report.text, "100 USD = 92.15 EUR (rate 0.9215)");
+        assert_eq!(report.json["target"], json!({"amount": "92.15", "currency": "EUR"}));
+
+        // Fixed rates apply at any date and are inverted when needed
+        let report = run_args(&["convert", "127", "USD", "GBP", "--rates", &rates]).unwrap();
+        assert_eq!(report.json["target"], json!({"amount": "100.00", "currency": "GBP"}));
+
+        let err = run_args(&["convert", "1", "USD", "JPY", "--rates", &rates]).err().unwrap();
+        assert_eq!(err.code(), "exchange.no_rate_found");
+        fs::remove_file(&rates).unwrap();
+
+        let err = run_args(&["convert", "1", "USD", "EUR", "--rates", "/nonexistent/rates.csv"]).err().unwrap();
+        assert_eq!(err.code(), "config.io");
+    }
+
+    #[test]
+    fn test_format_parse_allocate() {
+        let report = run_args(&["format", "1234.5", "EUR", "--locale", "de-DE"]).unwrap();
+        assert_eq!(report.text, "1.234,50\u{a0}€");
+
+        let report = run_args(&["parse", "$1,234.56"]).unwrap();
+        assert_eq!(report.json, json!({"amount": "1234.56", "currency": "USD"}));
+
+        let report = run_args(&["allocate", "100", "USD", "3"]).unwrap();
+        assert_eq!(report.text, "33.34 USD\n33.33 USD\n33.33 USD");
+
+        assert_eq!(run_args(&["format", "1", "EUR", "--locale", "xx-XX"]).err().unwrap().code(), "currency.invalid_format");
+        assert_eq!(run_args(&["allocate", "1", "ZZZ", "2"]).err().unwrap().code(), "currency.unknown_currency");
+    }
+
+    #[test]
+    fn test_currencies_list() {
+        let report = run_args(&["currencies", "list"]).unwrap();
+        assert!(report.text.lines().any(|line| line.starts_with("USD   840  2 $    ")));
+        let hrk = report.json.as_array().unwrap().iter().find(|c| c["code"] == "HRK").unwrap();
+        assert_eq!(hrk["withdrawn_on"], "2023-01-01");
+    }
 }
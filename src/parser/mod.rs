@@ -0,0 +1,497 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use rust_decimal::Decimal;
+use crate::core::{Monetary, Monetizable};
+use crate::core::currency::Currency;
+use crate::core::money::Money;
+use crate::core::registry::CurrencyRegistry;
+use crate::errors::CurrencyError;
+use crate::formatter::Locale;
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Open,
+    Close,
+    Sign { negative: bool },
+    Number(String),
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    offset: usize,
+}
+
+fn is_sign(c: char) -> bool {
+    matches!(c, '-' | '+' | '\u{2212}')
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '.' | ',' | '\'' | '\u{2019}') || c.is_whitespace()
+}
+
+fn next_char(input: &str, offset: usize) -> Option<char> {
+    input[offset..].chars().next()
+}
+
+/// Parser for human-written money strings such as "$1,234.56", "1.234,56 €",
+/// "EUR 12", "-£3.50" or "(1,000.00) USD".
+///
+/// Separators are detected automatically: when both '.' and ',' appear the last one
+/// is the decimal separator; a lone ',' followed by exactly three digits is treated as
+/// grouping. Use `with_decimal_separator` or `for_locale` to remove the ambiguity.
+#[derive(Debug, Clone)]
+pub struct MoneyParser {
+    decimal_separator: Option<char>,
+    symbols: HashMap<String, Currency>,
+    registry: Option<Arc<CurrencyRegistry>>,
+}
+
+impl MoneyParser {
+    /// Parser with automatic separator detection. The ambiguous symbols "$" and "¥"
+    /// resolve to USD and JPY.
+    pub fn new() -> Self {
+        let mut symbols = HashMap::new();
+        symbols.insert("$".to_string(), Currency::usd());
+        symbols.insert("¥".to_string(), Currency::jpy());
+        Self {
+            decimal_separator: None,
+            symbols,
+            registry: None,
+        }
+    }
+
+    /// Parser using the decimal separator of a formatter locale
+    pub fn for_locale(locale: Locale) -> Self {
+        let separator = locale.style().decimal_separator.chars().next();
+        Self {
+            decimal_separator: separator,
+            ..Self::new()
+        }
+    }
+
+    pub fn with_decimal_separator(mut self, separator: char) -> Self {
+        self.decimal_separator = Some(separator);
+        self
+    }
+
+    /// Resolve `symbol` to `currency`, overriding registry lookup (e.g. "kr" -> SEK)
+    pub fn with_symbol(mut self, symbol: &str, currency: Currency) -> Self {
+        self.symbols.insert(symbol.to_string(), currency);
+        self
+    }
+
+    /// Resolve codes and symbols against `registry` instead of the global one
+    pub fn with_registry(mut self, registry: Arc<CurrencyRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    fn registry(&self) -> &CurrencyRegistry {
+        self.registry.as_deref().unwrap_or(CurrencyRegistry::global())
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Monetary<Decimal>, CurrencyError> {
+        let tokens = Self::tokenize(input);
+
+        let mut negative = false;
+        let mut sign_seen = false;
+        let mut open: Option<usize> = None;
+        let mut closed = false;
+        let mut number: Option<(String, usize)> = None;
+        let mut text: Option<(String, usize)> = None;
+
+        for token in tokens {
+            let offset = token.offset;
+            match token.kind {
+                TokenKind::Open => {
+                    if open.is_some() || sign_seen || number.is_some() {
+                        return Err(CurrencyError::invalid_format_at("Unexpected '('", input, offset));
+                    }
+                    open = Some(offset);
+                }
+                TokenKind::Close => {
+                    if open.is_none() || closed || number.is_none() {
+                        return Err(CurrencyError::invalid_format_at("Unexpected ')'", input, offset));
+                    }
+                    closed = true;
+                }
+                TokenKind::Sign { negative: is_negative } => {
+                    if sign_seen || number.is_some() || open.is_some() {
+                        return Err(CurrencyError::invalid_format_at("Unexpected sign", input, offset));
+                    }
+                    sign_seen = true;
+                    negative = is_negative;
+                }
+                TokenKind::Number(raw) => {
+                    if number.is_some() || closed {
+                        return Err(CurrencyError::invalid_format_at("Unexpected second amount", input, offset));
+                    }
+                    number = Some((raw, offset));
+                }
+                TokenKind::Text(raw) => {
+                    if text.is_some() {
+                        return Err(CurrencyError::invalid_format_at(
+                            format!("Unexpected text '{}'", raw),
+                            input,
+                            offset,
+                        ));
+                    }
+                    text = Some((raw, offset));
+                }
+            }
+        }
+
+        if open.is_some() && !closed {
+            return Err(CurrencyError::invalid_format_at("Missing closing ')'", input, input.len()));
+        }
+        let (raw_number, number_offset) = number
+            .ok_or_else(|| CurrencyError::invalid_format_at("Missing amount", input, input.len()))?;
+        let (raw_text, text_offset) = text
+            .ok_or_else(|| CurrencyError::invalid_format_at("Missing currency", input, input.len()))?;
+
+        let amount = self.parse_number(&raw_number, number_offset, input)?;
+        let currency = self.resolve_currency(&raw_text, text_offset, input)?;
+
+        let amount = if negative || open.is_some() { -amount } else { amount };
+        Ok(Monetary::new(amount, currency))
+    }
+
+    pub fn parse_money(&self, input: &str) -> Result<Money, CurrencyError> {
+        let parsed = self.parse(input)?;
+        Money::new(parsed.currency.code(), parsed.amount)
+    }
+
+    fn tokenize(input: &str) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        let mut chars = input.char_indices().peekable();
+
+        while let Some((offset, c)) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+
+            let starts_number = c.is_ascii_digit()
+                || (matches!(c, '.' | ',') && next_char(input, offset + c.len_utf8()).is_some_and(|n| n.is_ascii_digit()));
+
+            let kind = match c {
+                '(' => TokenKind::Open,
+                ')' => TokenKind::Close,
+                c if is_sign(c) => TokenKind::Sign { negative: c != '+' },
+                _ if starts_number => {
+                    let mut end = offset + c.len_utf8();
+                    while let Some(&(i, n)) = chars.peek() {
+                        // Whitespace only counts as a grouping separator between two digits
+                        let keep = n.is_ascii_digit()
+                            || (is_separator(n) && !n.is_whitespace())
+                            || (n.is_whitespace()
+                                && input[..i].ends_with(|p: char| p.is_ascii_digit())
+                                && next_char(input, i + n.len_utf8()).is_some_and(|d| d.is_ascii_digit()));
+                        if !keep {
+                            break;
+                        }
+                        end = i + n.len_utf8();
+                        chars.next();
+                    }
+                    TokenKind::Number(input[offset..end].to_string())
+                }
+                _ => {
+                    let mut end = offset + c.len_utf8();
+                    while let Some(&(i, n)) = chars.peek() {
+                        let number_ahead = matches!(n, '.' | ',')
+                            && next_char(input, i + n.len_utf8()).is_some_and(|d| d.is_ascii_digit());
+                        if n.is_whitespace() || n.is_ascii_digit() || is_sign(n) || n == '(' || n == ')' || number_ahead {
+                            break;
+                        }
+                        end = i + n.len_utf8();
+                        chars.next();
+                    }
+                    TokenKind::Text(input[offset..end].to_string())
+                }
+            };
+            tokens.push(Token { kind, offset });
+        }
+
+        tokens
+    }
+
+    fn parse_number(&self, raw: &str, offset: usize, input: &str) -> Result<Decimal, CurrencyError> {
+        let chars: Vec<(usize, char)> = raw.char_indices().collect();
+        let separators: Vec<(usize, char)> = chars.iter().copied().filter(|(_, c)| !c.is_ascii_digit()).collect();
+
+        // Every separator must sit between two digits
+        for (idx, &(pos, c)) in chars.iter().enumerate() {
+            if c.is_ascii_digit() {
+                continue;
+            }
+            let digit_before = idx > 0 && chars[idx - 1].1.is_ascii_digit();
+            let digit_after = chars.get(idx + 1).is_some_and(|(_, n)| n.is_ascii_digit());
+            // ".50" is allowed: a leading separator is a decimal point
+            if !(digit_after && (digit_before || idx == 0)) {
+                return Err(CurrencyError::invalid_format_at(
+                    format!("Misplaced separator '{}'", c),
+                    input,
+                    offset + pos,
+                ));
+            }
+        }
+
+        let decimal_pos = match self.decimal_separator {
+            Some(separator) => separators.iter().rev().find(|(_, c)| *c == separator).map(|(pos, _)| *pos),
+            None => Self::detect_decimal(raw, &separators),
+        };
+
+        if let Some(separator) = self.decimal_separator {
+            let occurrences: Vec<usize> = separators.iter().filter(|(_, c)| *c == separator).map(|(pos, _)| *pos).collect();
+            if occurrences.len() > 1 {
+                return Err(CurrencyError::invalid_format_at(
+                    format!("Repeated decimal separator '{}'", separator),
+                    input,
+                    offset + occurrences[1],
+                ));
+            }
+        }
+
+        if let Some(decimal) = decimal_pos {
+            if let Some((pos, c)) = separators.iter().find(|(pos, _)| *pos > decimal) {
+                return Err(CurrencyError::invalid_format_at(
+                    format!("Unexpected separator '{}' after decimal separator", c),
+                    input,
+                    offset + pos,
+                ));
+            }
+        }
+
+        // Grouped digits: inner groups have 2 (Indian style) or 3 digits, the last group 3
+        let integer_end = decimal_pos.unwrap_or(raw.len());
+        let group_starts: Vec<usize> = separators
+            .iter()
+            .filter(|(pos, _)| *pos < integer_end)
+            .map(|(pos, c)| pos + c.len_utf8())
+            .collect();
+        for (idx, start) in group_starts.iter().enumerate() {
+            let end = separators
+                .iter()
+                .map(|(pos, _)| *pos)
+                .find(|pos| pos >= start)
+                .unwrap_or(raw.len())
+                .min(integer_end);
+            let size = end - start;
+            let is_last = idx + 1 == group_starts.len();
+            if (is_last && size != 3) || (!is_last && !(2..=3).contains(&size)) {
+                return Err(CurrencyError::invalid_format_at(
+                    "Invalid digit grouping",
+                    input,
+                    offset + start - 1,
+                ));
+            }
+        }
+
+        let mut normalized = String::with_capacity(raw.len());
+        for &(pos, c) in &chars {
+            if c.is_ascii_digit() {
+                normalized.push(c);
+            } else if Some(pos) == decimal_pos {
+                normalized.push('.');
+            }
+        }
+
+        Decimal::from_str(&normalized)
+            .map_err(|e| CurrencyError::invalid_format_at(format!("Invalid amount: {}", e), input, offset))
+    }
+
+    /// Pick the decimal separator when no locale hint is configured
+    fn detect_decimal(raw: &str, separators: &[(usize, char)]) -> Option<usize> {
+        let last = separators.iter().rev().find(|(_, c)| matches!(c, '.' | ','))?;
+        let same_kind = separators.iter().filter(|(_, c)| *c == last.1).count();
+        let other_kind = separators.iter().any(|(_, c)| matches!(c, '.' | ',') && *c != last.1);
+
+        if other_kind {
+            // "1.234,56" / "1,234.56": the rightmost kind is the decimal separator
+            return if same_kind == 1 { Some(last.0) } else { None };
+        }
+        if same_kind > 1 {
+            return None;
+        }
+
+        let digits_after = raw[last.0 + 1..].chars().take_while(|c| c.is_ascii_digit()).count();
+        let integer_is_zero = raw[..last.0].chars().filter(|c| c.is_ascii_digit()).all(|c| c == '0');
+        if last.1 == ',' && digits_after == 3 && !integer_is_zero {
+            None
+        } else {
+            Some(last.0)
+        }
+    }
+
+    fn resolve_currency(&self, text: &str, offset: usize, input: &str) -> Result<Currency, CurrencyError> {
+        if let Some(currency) = self.symbols.get(text) {
+            return Ok(currency.clone());
+        }
+
+        if text.chars().all(|c| c.is_ascii_alphanumeric()) {
+            if let Some(currency) = self.registry().get(text) {
+                return Ok(currency);
+            }
+        }
+
+        // Withdrawn currencies only count when no active one uses the symbol
+        let mut matches = self.registry().find_by_symbol(text);
+        if matches.iter().any(Currency::is_active) {
+            matches.retain(Currency::is_active);
+        }
+
+        match matches.len() {
+            1 => Ok(matches[0].clone()),
+            0 => Err(CurrencyError::invalid_format_at(
+                format!("Unknown currency '{}'", text),
+                input,
+                offset,
+            )),
+            _ => Err(CurrencyError::invalid_format_at(
+                format!("Ambiguous currency symbol '{}'", text),
+                input,
+                offset,
+            )),
+        }
+    }
+}
+
+impl Default for MoneyParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parse a money string with the default parser
+pub fn parse_monetary(input: &str) -> Result<Monetary<Decimal>, CurrencyError> {
+    MoneyParser::new().parse(input)
+}
+
+/// Parse a money string into the `Money` enum with the default parser
+pub fn parse_money(input: &str) -> Result<Money, CurrencyError> {
+    MoneyParser::new().parse_money(input)
+}
+
+impl<T: Monetizable> FromStr for Monetary<T> {
+    type Err = CurrencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_monetary(s)?
+            .as_type::<T>()
+            .map_err(|e| CurrencyError::invalid_amount(s, e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    fn position(err: CurrencyError) -> Option<usize> {
+        match err {
+            CurrencyError::InvalidFormat { position, .. } => position,
+            other => panic!("Expected InvalidFormat, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_common_shapes() {
+        let cases = vec![
+            ("$1,234.56", "1234.56", "USD"),
+            ("1.234,56 €", "1234.56", "EUR"),
+            ("EUR 12", "12", "EUR"),
+            ("12 JPY", "12", "JPY"),
+            ("-£3.50", "-3.50", "GBP"),
+            ("(1,000.00) USD", "-1000.00", "USD"),
+            ("£-3.50", "-3.50", "GBP"),
+            ("12€", "12", "EUR"),
+            ("usd 0.5", "0.5", "USD"),
+            ("CHF 1'234.50", "1234.50", "CHF"),
+            ("1\u{202f}234,56\u{a0}€", "1234.56", "EUR"),
+            ("₹1,23,456.78", "123456.78", "INR"),
+            ("R$ 10,5", "10.5", "BRL"),
+        ];
+
+        for (input, amount, code) in cases {
+            let parsed = parse_monetary(input).unwrap_or_else(|e| panic!("Failed for input {}: {}", input, e));
+            assert_eq!(parsed.amount, dec(amount), "Failed amount for input: {}", input);
+            assert_eq!(parsed.currency.code(), code, "Failed currency for input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_separator_detection() {
+        // A single comma followed by three digits is grouping, otherwise decimal
+        assert_eq!(parse_monetary("1,234 USD").unwrap().amount, dec("1234"));
+        assert_eq!(parse_monetary("1,5 EUR").unwrap().amount, dec("1.5"));
+        assert_eq!(parse_monetary("0,125 KWD").unwrap().amount, dec("0.125"));
+        assert_eq!(parse_monetary("1.234.567 EUR").unwrap().amount, dec("1234567"));
+
+        // A locale hint removes the ambiguity
+        let german = MoneyParser::for_locale(Locale::DeDe);
+        assert_eq!(german.parse("1.234 €").unwrap().amount, dec("1234"));
+        assert_eq!(german.parse("1,234 €").unwrap().amount, dec("1.234"));
+    }
+
+    #[test]
+    fn test_symbol_resolution() {
+        assert_eq!(parse_monetary("¥500").unwrap().currency, Currency::jpy());
+
+        // "kr" is shared by several currencies
+        let err = parse_monetary("100 kr").unwrap_err();
+        assert_eq!(position(err), Some(4));
+
+        let swedish = MoneyParser::new().with_symbol("kr", Currency::sek());
+        assert_eq!(swedish.parse("100 kr").unwrap().currency, Currency::sek());
+    }
+
+    #[test]
+    fn test_scoped_registry() {
+        use crate::core::currency_unit::CurrencyUnit;
+
+        let points = Currency::new(CurrencyUnit::new("PTS", 0, 0, "Loyalty Points"), "pts");
+        let registry = CurrencyRegistry::global().clone();
+        registry.register(points.clone()).unwrap();
+        let parser = MoneyParser::new().with_registry(Arc::new(registry));
+
+        assert_eq!(parser.parse("1,500 pts").unwrap().currency, points);
+        assert_eq!(parser.parse("PTS 20").unwrap().amount, dec("20"));
+        assert!(parse_monetary("20 PTS").is_err());
+    }
+
+    #[test]
+    fn test_error_positions() {
+        assert_eq!(position(parse_monetary("$12x").unwrap_err()), Some(3));
+        assert_eq!(position(parse_monetary("12 ABC").unwrap_err()), Some(3));
+        assert_eq!(position(parse_monetary("USD").unwrap_err()), Some(3));
+        assert_eq!(position(parse_monetary("12.50").unwrap_err()), Some(5));
+        assert_eq!(position(parse_monetary("(12 USD").unwrap_err()), Some(7));
+        assert_eq!(position(parse_monetary("$12,34,5.00").unwrap_err()), Some(6));
+        assert_eq!(position(parse_monetary("$1.234,56.7").unwrap_err()), Some(9));
+        assert_eq!(position(parse_monetary("--$5").unwrap_err()), Some(1));
+        assert_eq!(position(parse_monetary("$5 6").unwrap_err()), Some(2));
+        assert_eq!(position(MoneyParser::for_locale(Locale::DeDe).parse("1,2,3 €").unwrap_err()), Some(3));
+    }
+
+    #[test]
+    fn test_parse_money_enum() {
+        assert_eq!(parse_money("$1,234.56").unwrap(), Money::usd(1234.56));
+        assert_eq!(parse_money("-£3.50").unwrap(), Money::gbp(-3.5));
+        assert_eq!(Money::from_str("1.234,56 €").unwrap(), Money::eur(1234.56));
+        assert_eq!(Money::from_str("USD:10.50").unwrap(), Money::usd(10.5));
+    }
+
+    #[test]
+    fn test_monetary_from_str() {
+        let parsed: Monetary<f64> = "EUR 12.25".parse().unwrap();
+        assert_eq!(parsed.amount, 12.25);
+        assert_eq!(parsed.currency, Currency::eur());
+
+        assert!("twelve dollars".parse::<Monetary<Decimal>>().is_err());
+    }
+}
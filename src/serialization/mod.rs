@@ -0,0 +1,727 @@
+// Serialization
+// =============
+//
+// serde support, enabled with the `serde` cargo feature. Currencies serialize as their
+// code and are checked against `CurrencyRegistry::global()` when deserialized. Amounts
+// are written as exact decimal strings and are read from strings or integers, never
+// through an f64.
+//
+// `Monetary` uses the object form by default. The `object`, `code_amount` and
+// `minor_units` modules pick a representation per field:
+//
+//     #[derive(Serialize, Deserialize)]
+//     struct Invoice {
+//         // {"amount":"12.50","currency":"USD"}
+//         total: Monetary<Decimal>,
+//         // "USD 1.25"
+//         #[serde(with = "monetary::serialization::code_amount")]
+//         tax: Monetary<Decimal>,
+//         // {"currency":"USD","minor_units":499}
+//         #[serde(with = "monetary::serialization::minor_units")]
+//         shipping: Monetary<Decimal>,
+//     }
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::ser::{self, Serializer};
+use serde::{Deserialize, Serialize};
+use crate::configs::ContextOverride;
+use crate::constants::RoundingMode;
+use crate::core::currency::Currency;
+use crate::core::currency_unit::CurrencyUnit;
+use crate::core::money::Money;
+use crate::core::money_bag::MoneyBag;
+use crate::core::registry::CurrencyRegistry;
+use crate::core::types::BigDecimal;
+use crate::core::{Monetary, MonetaryContext, Monetizable};
+use crate::exchange::base_exchange::ExchangeRate;
+use crate::exchange::receipt::ConversionReceipt;
+
+// =======================
+// Currencies
+// =======================
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        lookup_currency(&code)
+    }
+}
+
+impl Serialize for CurrencyUnit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.get_code())
+    }
+}
+
+impl<'de> Deserialize<'de> for CurrencyUnit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Currency::deserialize(deserializer)?.get_unit().clone())
+    }
+}
+
+fn lookup_currency<E: de::Error>(code: &str) -> Result<Currency, E> {
+    CurrencyRegistry::global()
+        .get(code)
+        .ok_or_else(|| E::custom(format!("unknown currency code '{}'", code)))
+}
+
+// =======================
+// Context
+// =======================
+
+impl Serialize for RoundingMode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> Deserialize<'de> for RoundingMode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        RoundingMode::from_str(&name).map_err(de::Error::custom)
+    }
+}
+
+#[derive(Serialize)]
+struct ContextRef<'a> {
+    precision: u32,
+    max_scale: i32,
+    rounding_mode: &'a RoundingMode,
+}
+
+// Missing fields fall back to the default context
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawContext {
+    precision: Option<u32>,
+    max_scale: Option<i32>,
+    rounding_mode: Option<RoundingMode>,
+}
+
+impl Serialize for MonetaryContext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ContextRef {
+            precision: self.precision(),
+            max_scale: self.max_scale(),
+            rounding_mode: self.rounding_mode(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MonetaryContext {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawContext::deserialize(deserializer)?;
+        let context = ContextOverride {
+            precision: raw.precision,
+            max_scale: raw.max_scale,
+            rounding_mode: raw.rounding_mode,
+        };
+        Ok(context.apply(&MonetaryContext::default()))
+    }
+}
+
+// =======================
+// Amounts
+// =======================
+
+/// Decimal read from a string or an integer; floats are rejected
+struct DecimalAmount(Decimal);
+
+impl<'de> Deserialize<'de> for DecimalAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(DecimalAmountVisitor).map(DecimalAmount)
+    }
+}
+
+struct DecimalAmountVisitor;
+
+impl<'de> Visitor<'de> for DecimalAmountVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a decimal string or an integer")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Decimal, E> {
+        Decimal::from_str_exact(value.trim()).map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Decimal, E> {
+        Ok(Decimal::from(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Decimal, E> {
+        Ok(Decimal::from(value))
+    }
+}
+
+fn amount_from_decimal<T: Monetizable, E: de::Error>(value: Decimal) -> Result<T, E> {
+    T::try_from_decimal(value).map_err(E::custom)
+}
+
+impl Serialize for BigDecimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for BigDecimal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(BigDecimalVisitor)
+    }
+}
+
+struct BigDecimalVisitor;
+
+impl<'de> Visitor<'de> for BigDecimalVisitor {
+    type Value = BigDecimal;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a decimal string or an integer")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<BigDecimal, E> {
+        BigDecimal::from_str(value).map_err(|_| E::invalid_value(Unexpected::Str(value), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<BigDecimal, E> {
+        Ok(BigDecimal::from_i64(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<BigDecimal, E> {
+        Ok(BigDecimal::new(value as i128, 0))
+    }
+}
+
+// =======================
+// Monetary
+// =======================
+
+impl<T: Monetizable + 'static> Serialize for Monetary<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        object::serialize(self, serializer)
+    }
+}
+
+/// The context is not serialized; deserialized values use the default context
+impl<'de, T: Monetizable + 'static> Deserialize<'de> for Monetary<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        object::deserialize(deserializer)
+    }
+}
+
+#[derive(Serialize)]
+struct ObjectRef<'a> {
+    amount: String,
+    currency: &'a Currency,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawObject {
+    amount: DecimalAmount,
+    currency: Currency,
+}
+
+/// `{"amount":"12.50","currency":"USD"}`, the default representation
+pub mod object {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &Monetary<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Monetizable + 'static,
+        S: Serializer,
+    {
+        ObjectRef {
+            amount: value.amount.to_string(),
+            currency: &value.currency,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Monetary<T>, D::Error>
+    where
+        T: Monetizable + 'static,
+        D: Deserializer<'de>,
+    {
+        let raw = RawObject::deserialize(deserializer)?;
+        Ok(Monetary::new(amount_from_decimal(raw.amount.0)?, raw.currency))
+    }
+}
+
+/// `"USD 12.50"`: currency code, a single space, then the amount
+pub mod code_amount {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &Monetary<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Monetizable + 'static,
+        S: Serializer,
+    {
+        serializer.collect_str(&format_args!("{} {}", value.currency.code(), value.amount))
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Monetary<T>, D::Error>
+    where
+        T: Monetizable + 'static,
+        D: Deserializer<'de>,
+    {
+        let text = String::deserialize(deserializer)?;
+        let (code, amount) = text
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| de::Error::invalid_value(Unexpected::Str(&text), &"\"<CODE> <amount>\""))?;
+        let currency = lookup_currency(code)?;
+        let amount = DecimalAmountVisitor.visit_str::<D::Error>(amount)?;
+        Ok(Monetary::new(amount_from_decimal(amount)?, currency))
+    }
+}
+
+/// `{"currency":"USD","minor_units":1250}`: an integer count of the currency's minor
+/// unit. Serializing fails if the amount has digits beyond the minor unit. The count is
+/// an i128, since 18-decimal currencies pass `i64::MAX` at about 9.2 whole units.
+pub mod minor_units {
+    use super::*;
+    use crate::core::minor_units::MinorUnits;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Repr<C> {
+        currency: C,
+        minor_units: i128,
+    }
+
+    pub fn serialize<T, S>(value: &Monetary<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Monetizable + 'static,
+        S: Serializer,
+    {
+        let units = value
+            .amount
+            .try_to_decimal()
+            .and_then(|amount| MinorUnits::<i128>::from_decimal_for(amount, &value.currency))
+            .map_err(|err| ser::Error::custom(format!("cannot express {} in minor units: {}", value, err)))?;
+        Repr {
+            currency: &value.currency,
+            minor_units: units.units(),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Monetary<T>, D::Error>
+    where
+        T: Monetizable + 'static,
+        D: Deserializer<'de>,
+    {
+        let repr = Repr::<Currency>::deserialize(deserializer)?;
+        let units = MinorUnits::<i128>::for_currency(repr.minor_units, &repr.currency).map_err(de::Error::custom)?;
+        let amount = units.try_to_decimal().map_err(de::Error::custom)?;
+        Ok(Monetary::new(amount_from_decimal(amount)?, repr.currency))
+    }
+}
+
+// =======================
+// Money
+// =======================
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ObjectRef {
+            amount: self.amount().to_string(),
+            currency: &self.currency(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawObject::deserialize(deserializer)?;
+        Money::new(raw.currency.code(), raw.amount.0).map_err(de::Error::custom)
+    }
+}
+
+// =======================
+// Money bag
+// =======================
+
+/// `{"EUR":"50.00","USD":"79.50"}`: one entry per currency code
+impl<T: Monetizable + 'static> Serialize for MoneyBag<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter().map(|balance| (balance.currency.code(), balance.amount.to_string())))
+    }
+}
+
+impl<'de, T: Monetizable + 'static> Deserialize<'de> for MoneyBag<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = BTreeMap::<String, DecimalAmount>::deserialize(deserializer)?;
+        let mut bag = MoneyBag::new();
+        for (code, amount) in raw {
+            let balance = Monetary::new(amount_from_decimal(amount.0)?, lookup_currency(&code)?);
+            bag.add(&balance).map_err(de::Error::custom)?;
+        }
+        Ok(bag)
+    }
+}
+
+// =======================
+// Exchange rates
+// =======================
+
+#[derive(Serialize)]
+struct RateRef<'a> {
+    base: &'a Currency,
+    target: &'a Currency,
+    rate: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bid: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ask: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ttl_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid_from: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid_to: Option<DateTime<Utc>>,
+    context: &'a MonetaryContext,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawRate {
+    base: Currency,
+    target: Currency,
+    rate: DecimalAmount,
+    #[serde(default)]
+    bid: Option<DecimalAmount>,
+    #[serde(default)]
+    ask: Option<DecimalAmount>,
+    #[serde(default)]
+    provider: Option<String>,
+    #[serde(default)]
+    ttl_ms: Option<u64>,
+    #[serde(default)]
+    valid_from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    valid_to: Option<DateTime<Utc>>,
+    #[serde(default)]
+    context: Option<MonetaryContext>,
+}
+
+impl<T: Monetizable> Serialize for ExchangeRate<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RateRef {
+            base: self.get_base_currency(),
+            target: self.get_target_currency(),
+            rate: self.get_factor().to_string(),
+            bid: self.has_quotes().then(|| self.get_bid().to_string()),
+            ask: self.has_quotes().then(|| self.get_ask().to_string()),
+            provider: self.get_provider(),
+            ttl_ms: self.get_ttl().map(|ttl| ttl.as_millis() as u64),
+            valid_from: self.get_valid_from(),
+            valid_to: self.get_valid_to(),
+            context: self.get_context(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// A deserialized rate's time-to-live starts counting when it is deserialized. `bid`
+/// and `ask` are given together or not at all.
+impl<'de, T: Monetizable> Deserialize<'de> for ExchangeRate<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawRate::deserialize(deserializer)?;
+        let mut rate = ExchangeRate::new(raw.base, raw.target, amount_from_decimal(raw.rate.0)?)
+            .with_context(raw.context.unwrap_or_default());
+        if let Some(ttl) = raw.ttl_ms {
+            rate = rate.with_ttl(Duration::from_millis(ttl));
+        }
+        if let Some(valid_from) = raw.valid_from {
+            rate = rate.with_valid_from(valid_from);
+        }
+        if let Some(valid_to) = raw.valid_to {
+            rate = rate.with_valid_to(valid_to);
+        }
+        if let Some(provider) = raw.provider {
+            rate = rate.with_provider(provider);
+        }
+        match (raw.bid, raw.ask) {
+            (Some(bid), Some(ask)) => rate = rate.with_quotes(amount_from_decimal(bid.0)?, amount_from_decimal(ask.0)?),
+            (None, None) => {}
+            _ => return Err(de::Error::custom("bid and ask must be given together")),
+        }
+        Ok(rate)
+    }
+}
+
+// =======================
+// Conversion receipts
+// =======================
+
+#[derive(Serialize)]
+#[serde(bound = "")]
+struct ReceiptRef<'a, T: Monetizable + 'static> {
+    source: &'a Monetary<T>,
+    target: &'a Monetary<T>,
+    rate: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider: Option<&'a str>,
+    rate_timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid_from: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    valid_to: Option<DateTime<Utc>>,
+    context: &'a MonetaryContext,
+    residual: String,
+    issued_at: DateTime<Utc>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, bound = "")]
+struct RawReceipt<T: Monetizable + 'static> {
+    source: Monetary<T>,
+    target: Monetary<T>,
+    rate: DecimalAmount,
+    #[serde(default)]
+    provider: Option<String>,
+    rate_timestamp: DateTime<Utc>,
+    #[serde(default)]
+    valid_from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    valid_to: Option<DateTime<Utc>>,
+    context: MonetaryContext,
+    residual: DecimalAmount,
+    issued_at: DateTime<Utc>,
+}
+
+impl<T: Monetizable + 'static> Serialize for ConversionReceipt<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ReceiptRef {
+            source: &self.source,
+            target: &self.target,
+            rate: self.rate.to_string(),
+            provider: self.provider.as_deref(),
+            rate_timestamp: self.rate_timestamp,
+            valid_from: self.valid_from,
+            valid_to: self.valid_to,
+            context: &self.context,
+            residual: self.residual.to_string(),
+            issued_at: self.issued_at,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, T: Monetizable + 'static> Deserialize<'de> for ConversionReceipt<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawReceipt::<T>::deserialize(deserializer)?;
+        Ok(ConversionReceipt {
+            source: raw.source,
+            target: raw.target,
+            rate: amount_from_decimal(raw.rate.0)?,
+            provider: raw.provider,
+            rate_timestamp: raw.rate_timestamp,
+            valid_from: raw.valid_from,
+            valid_to: raw.valid_to,
+            context: raw.context,
+            residual: raw.residual.0,
+            issued_at: raw.issued_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn usd(value: &str) -> Monetary<Decimal> {
+        Monetary::new(Decimal::from_str(value).unwrap(), Currency::usd())
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Invoice {
+        total: Monetary<Decimal>,
+        #[serde(with = "code_amount")]
+        tax: Monetary<Decimal>,
+        #[serde(with = "minor_units")]
+        shipping: Monetary<Decimal>,
+    }
+
+    #[test]
+    fn test_currency_is_its_code() {
+        assert_eq!(serde_json::to_value(Currency::eur()).unwrap(), json!("EUR"));
+        assert_eq!(serde_json::from_value::<Currency>(json!("jpy")).unwrap(), Currency::jpy());
+        assert!(serde_json::from_value::<Currency>(json!("ZZZ")).is_err());
+        assert_eq!(serde_json::to_value(RoundingMode::HalfEven).unwrap(), json!("HALF_EVEN"));
+    }
+
+    #[test]
+    fn test_representations() {
+        let invoice = Invoice {
+            total: usd("12.50"),
+            tax: usd("1.25"),
+            shipping: usd("4.99"),
+        };
+        let value = serde_json::to_value(&invoice).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "total": {"amount": "12.50", "currency": "USD"},
+                "tax": "USD 1.25",
+                "shipping": {"currency": "USD", "minor_units": 499},
+            })
+        );
+        assert_eq!(serde_json::from_value::<Invoice>(value).unwrap(), invoice);
+
+        // Sub-cent amounts cannot be written as minor units
+        let precise = Invoice {
+            shipping: usd("4.995"),
+            ..invoice
+        };
+        assert!(serde_json::to_value(&precise).is_err());
+    }
+
+    #[test]
+    fn test_minor_units_for_18_decimal_currency() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Transfer {
+            #[serde(with = "minor_units")]
+            value: Monetary<Decimal>,
+        }
+
+        for amount in ["10", "1234.000000000000000001"] {
+            let transfer = Transfer {
+                value: Monetary::new(Decimal::from_str(amount).unwrap(), Currency::eth()),
+            };
+            let text = serde_json::to_string(&transfer).unwrap();
+            assert_eq!(serde_json::from_str::<Transfer>(&text).unwrap(), transfer);
+        }
+
+        let ten_eth = Transfer {
+            value: Monetary::new(Decimal::from(10), Currency::eth()),
+        };
+        assert_eq!(
+            serde_json::to_string(&ten_eth).unwrap(),
+            r#"{"value":{"currency":"ETH","minor_units":10000000000000000000}}"#
+        );
+    }
+
+    #[test]
+    fn test_amounts_never_go_through_floats() {
+        let money: Monetary<Decimal> =
+            serde_json::from_str(r#"{"amount":"0.1000000000000000000000000001","currency":"USD"}"#).unwrap();
+        assert_eq!(money.amount.to_string(), "0.1000000000000000000000000001");
+
+        let float = serde_json::from_str::<Monetary<Decimal>>(r#"{"amount":0.1,"currency":"USD"}"#);
+        assert!(float.is_err());
+        let integer: Monetary<BigDecimal> = serde_json::from_str(r#"{"amount":12,"currency":"USD"}"#).unwrap();
+        assert_eq!(integer.amount, BigDecimal::from_i64(12));
+
+        let big = BigDecimal::from_str("123456789012345678901234567890.12").unwrap();
+        assert_eq!(serde_json::to_string(&big).unwrap(), r#""123456789012345678901234567890.12""#);
+
+        assert_eq!(serde_json::to_value(Money::usd(0.1)).unwrap(), json!({"amount": "0.1", "currency": "USD"}));
+        assert!(serde_json::from_str::<Monetary<Decimal>>(r#"{"amount":"1","currency":"XYZ"}"#).is_err());
+    }
+
+    #[test]
+    fn test_money_bag_round_trip() {
+        let mut bag = MoneyBag::new();
+        bag.add(&usd("79.50")).unwrap();
+        bag.add(&Monetary::new(Decimal::from_str("0.015").unwrap(), Currency::btc())).unwrap();
+        let value = serde_json::to_value(&bag).unwrap();
+        assert_eq!(value, json!({"BTC": "0.015", "USD": "79.50"}));
+        assert_eq!(serde_json::from_value::<MoneyBag<Decimal>>(value).unwrap(), bag);
+
+        assert!(serde_json::from_value::<MoneyBag<Decimal>>(json!({"ZZZ": "1"})).is_err());
+        assert!(serde_json::from_value::<MoneyBag<Decimal>>(json!({"USD": 1.5})).is_err());
+    }
+
+    #[test]
+    fn test_exchange_rate_round_trip() {
+        let rate = ExchangeRate::new(Currency::usd(), Currency::eur(), Decimal::from_str("0.9215").unwrap())
+            .with_ttl(Duration::from_secs(60))
+            .with_context(MonetaryContext::new(19, 4, RoundingMode::Floor));
+        let value = serde_json::to_value(&rate).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "base": "USD",
+                "target": "EUR",
+                "rate": "0.9215",
+                "ttl_ms": 60000,
+                "context": {"precision": 19, "max_scale": 4, "rounding_mode": "FLOOR"},
+            })
+        );
+
+        let restored: ExchangeRate<Decimal> = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.get_factor(), rate.get_factor());
+        assert_eq!(restored.get_context(), rate.get_context());
+        assert_eq!(restored.get_ttl(), rate.get_ttl());
+
+        let minimal: ExchangeRate<f64> =
+            serde_json::from_value(json!({"base": "GBP", "target": "USD", "rate": "1.27"})).unwrap();
+        assert_eq!(*minimal.get_factor(), 1.27);
+        assert_eq!(minimal.get_context(), &MonetaryContext::default());
+
+        let dated: ExchangeRate<Decimal> = serde_json::from_value(json!({
+            "base": "EUR",
+            "target": "USD",
+            "rate": "1.0811",
+            "valid_from": "2024-03-28T16:00:00Z",
+        }))
+        .unwrap();
+        assert_eq!(dated.get_valid_from(), "2024-03-28T16:00:00Z".parse().ok());
+        assert_eq!(serde_json::to_value(&dated).unwrap()["valid_from"], json!("2024-03-28T16:00:00Z"));
+
+        let quoted = ExchangeRate::new(Currency::eur(), Currency::usd(), Decimal::from_str("1.0811").unwrap())
+            .with_quotes(Decimal::from_str("1.0801").unwrap(), Decimal::from_str("1.0821").unwrap());
+        let value = serde_json::to_value(&quoted).unwrap();
+        assert_eq!((&value["bid"], &value["ask"]), (&json!("1.0801"), &json!("1.0821")));
+        let restored: ExchangeRate<Decimal> = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.get_ask(), quoted.get_ask());
+        assert!(serde_json::from_value::<ExchangeRate<Decimal>>(json!({
+            "base": "EUR", "target": "USD", "rate": "1.0811", "bid": "1.0801",
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_conversion_receipt_round_trip() {
+        let valid_from = "2024-01-31T00:00:00Z".parse().unwrap();
+        let rate = ExchangeRate::new(Currency::usd(), Currency::eur(), Decimal::from_str("0.92154").unwrap())
+            .with_context(MonetaryContext::new(19, 2, RoundingMode::HalfUp))
+            .with_valid_from(valid_from)
+            .with_provider("file");
+        let receipt = rate.apply_with_receipt(&usd("10.00")).unwrap();
+
+        let value = serde_json::to_value(&receipt).unwrap();
+        assert_eq!(value["source"], json!({"amount": "10.00", "currency": "USD"}));
+        assert_eq!(value["target"], json!({"amount": "9.22", "currency": "EUR"}));
+        assert_eq!((&value["rate"], &value["residual"]), (&json!("0.92154"), &json!("-0.0046")));
+        assert_eq!(value["provider"], "file");
+        assert_eq!(value["valid_from"], "2024-01-31T00:00:00Z");
+        assert_eq!(value["context"]["rounding_mode"], "HALF_UP");
+        assert!(value.get("valid_to").is_none());
+
+        let restored: ConversionReceipt<Decimal> = serde_json::from_value(value).unwrap();
+        assert_eq!(restored, receipt);
+        assert_eq!(serde_json::to_value(&rate).unwrap()["provider"], "file");
+    }
+}